@@ -59,6 +59,11 @@ mod wasm {
         abi::wrap_call(arg_len, |(): ()| STATE.admin_nonce())
     }
 
+    #[no_mangle]
+    unsafe extern "C" fn event_sequence(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(): ()| STATE.event_sequence())
+    }
+
     #[no_mangle]
     unsafe extern "C" fn operators(arg_len: u32) -> u32 {
         abi::wrap_call(arg_len, |(): ()| STATE.operators())
@@ -38,6 +38,9 @@ pub struct AccessControl {
     // that are required for its execution. If the threshold for a call is set
     // to 0, a super-majority of signers is needed.
     operator_token_calls: BTreeMap<String, u8>,
+    // The sequence number of the last event emitted, initialized at 0 and
+    // strictly increasing.
+    event_sequence: u64,
 }
 
 /// The state of the access-control-contract at deployment.
@@ -55,6 +58,7 @@ impl AccessControl {
             operators: Vec::new(),
             operator_nonce: 0,
             operator_token_calls: BTreeMap::new(),
+            event_sequence: 0,
         }
     }
 
@@ -145,6 +149,19 @@ impl AccessControl {
         self.admin_nonce
     }
 
+    /// Return the sequence number of the last event emitted by the
+    /// access-control-contract.
+    #[must_use]
+    pub fn event_sequence(&self) -> u64 {
+        self.event_sequence
+    }
+
+    /// Return the sequence number of the next event emitted.
+    fn next_event_sequence(&mut self) -> u64 {
+        self.event_sequence += 1;
+        self.event_sequence
+    }
+
     /// Return the current operators stored in the access-control-contract.
     #[must_use]
     pub fn operators(&self) -> Vec<PublicKey> {
@@ -231,8 +248,11 @@ impl AccessControl {
         // alert network of the changes to the state
         abi::emit(
             events::UpdateToken::TOPIC,
-            events::UpdateToken {
-                contract: new_token_contract,
+            events::SequencedEvent {
+                sequence: self.next_event_sequence(),
+                event: events::UpdateToken {
+                    contract: new_token_contract,
+                },
             },
         );
 
@@ -293,7 +313,10 @@ impl AccessControl {
         // alert network of the changes to the state
         abi::emit(
             events::UpdatePublicKeys::NEW_ADMINS,
-            events::UpdatePublicKeys { pks: new_admins },
+            events::SequencedEvent {
+                sequence: self.next_event_sequence(),
+                event: events::UpdatePublicKeys { pks: new_admins },
+            },
         );
     }
 
@@ -347,7 +370,10 @@ impl AccessControl {
         // alert network of the changes to the state
         abi::emit(
             events::UpdatePublicKeys::NEW_OPERATORS,
-            events::UpdatePublicKeys { pks: new_operators },
+            events::SequencedEvent {
+                sequence: self.next_event_sequence(),
+                event: events::UpdatePublicKeys { pks: new_operators },
+            },
         );
     }
 
@@ -523,9 +549,12 @@ impl AccessControl {
         // alert network of the changes to the state
         abi::emit(
             events::UpdateTokenCall::TOPIC,
-            events::UpdateTokenCall {
-                call_name,
-                operator_signature_threshold,
+            events::SequencedEvent {
+                sequence: self.next_event_sequence(),
+                event: events::UpdateTokenCall {
+                    call_name,
+                    operator_signature_threshold,
+                },
             },
         );
     }
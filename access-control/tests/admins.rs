@@ -14,7 +14,8 @@ use emt_tests::utils::rkyv_serialize;
 
 pub mod common;
 use common::instantiate::{
-    TestKeys, TestSession, ACCESS_CONTROL_ID, INITIAL_BALANCE, TOKEN_ID,
+    TestKeys, TestSession, ACCESS_CONTROL_ID, INITIAL_BALANCE, TEST_REASON,
+    TOKEN_ID,
 };
 use common::{admin_signature, test_keys_signature};
 
@@ -56,6 +57,20 @@ fn set_token_contract() -> Result<(), ContractError> {
         .collect();
     assert_eq!(access_control_events.len(), 1);
     assert_eq!(access_control_events[0].topic, events::UpdateToken::TOPIC);
+    // check that the event is numbered as the first event
+    let event =
+        rkyv::from_bytes::<events::SequencedEvent<events::UpdateToken>>(
+            &access_control_events[0].data,
+        )
+        .expect("The event should deserialize");
+    assert_eq!(event.sequence, 1);
+    assert_eq!(event.event.contract, new_token_contract);
+    assert_eq!(
+        session
+            .query_access_control::<(), u64>("event_sequence", &())?
+            .data,
+        1,
+    );
     // check that the old contract-ID is returned
     assert_eq!(receipt.data, TOKEN_ID);
     // check that the token-contract on the access-control-contract updated
@@ -309,7 +324,8 @@ fn set_operators() -> Result<(), ContractError> {
     let mint_amount = 1000;
     let mint_receiver = Account::from(keys.test_pk[0]);
     let token_call_name = "mint".to_string();
-    let token_call_args = rkyv_serialize(&(mint_receiver, mint_amount));
+    let token_call_args =
+        rkyv_serialize(&(mint_receiver, mint_amount, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
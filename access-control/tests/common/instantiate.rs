@@ -19,7 +19,7 @@ use rkyv::{Archive, Deserialize, Infallible, Serialize};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 
-use emt_core::Account;
+use emt_core::{Account, Reason};
 
 use emt_tests::network::NetworkSession;
 
@@ -37,6 +37,10 @@ pub const ACCESS_CONTROL_ID: ContractId = ContractId::from_bytes([2; 32]);
 
 pub const INITIAL_BALANCE: u64 = 1000;
 
+/// The reason given for administrative actions in the tests.
+#[allow(dead_code)]
+pub const TEST_REASON: Reason = Reason::new(1, [42; 32]);
+
 type Result<T, Error = VMError> = core::result::Result<T, Error>;
 
 pub struct TestKeys<const O: usize, const P: usize, const H: usize> {
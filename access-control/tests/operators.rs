@@ -11,7 +11,7 @@ use emt_tests::utils::rkyv_serialize;
 
 pub mod common;
 use common::instantiate::{
-    TestKeys, TestSession, ACCESS_CONTROL_ID, INITIAL_BALANCE,
+    TestKeys, TestSession, ACCESS_CONTROL_ID, INITIAL_BALANCE, TEST_REASON,
 };
 use common::{operator_signature, test_keys_signature};
 
@@ -77,7 +77,7 @@ fn freeze_operator_token_call() -> Result<(), ContractError> {
     // generate signature
     let token_call_name = String::from("freeze");
     let freeze_account = Account::External(keys.test_pk[0]);
-    let token_call_args = rkyv_serialize(&freeze_account);
+    let token_call_args = rkyv_serialize(&(freeze_account, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
@@ -123,7 +123,7 @@ fn invalid_signature_operator_token_call_fails() -> Result<(), ContractError> {
     // generate signature with the admin keys
     let token_call_name = String::from("freeze");
     let freeze_account = Account::External(keys.test_pk[0]);
-    let token_call_args = rkyv_serialize(&freeze_account);
+    let token_call_args = rkyv_serialize(&(freeze_account, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
@@ -173,7 +173,7 @@ fn burn_operator_token_call() -> Result<(), ContractError> {
     // generate signature
     let token_call_name = String::from("burn");
     let burn_amount = 1000u64;
-    let token_call_args = rkyv_serialize(&burn_amount);
+    let token_call_args = rkyv_serialize(&(burn_amount, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
@@ -223,7 +223,7 @@ fn burn_operator_token_call() -> Result<(), ContractError> {
     // generate signature
     let token_call_name = String::from("burn");
     let burn_amount = 1000u64;
-    let token_call_args = rkyv_serialize(&burn_amount);
+    let token_call_args = rkyv_serialize(&(burn_amount, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
@@ -273,7 +273,8 @@ fn force_transfer_operator_token_call() -> Result<(), ContractError> {
     let obliged_sender = Account::from(keys.test_pk[3]);
     let receiver = Account::from(keys.test_pk[5]);
     let value = 100u64;
-    let token_call_args = rkyv_serialize(&(obliged_sender, receiver, value));
+    let token_call_args =
+        rkyv_serialize(&(obliged_sender, receiver, value, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
@@ -384,7 +385,7 @@ fn set_operator_token_call() -> Result<(), ContractError> {
     );
     // check call with previous lower threshold now panics
     let block_account = Account::External(keys.test_pk[0]);
-    let token_call_args = rkyv_serialize(&block_account);
+    let token_call_args = rkyv_serialize(&(block_account, TEST_REASON));
     let sig_msg = signature_messages::operator_token_call(
         operator_nonce,
         token_call_name.as_str(),
@@ -13,6 +13,10 @@ use rkyv::{Archive, Deserialize, Serialize};
 use dusk_core::abi::ContractId;
 use dusk_core::signatures::bls::PublicKey;
 
+/// Wrapper of every event emitted by the access-control-contract,
+/// numbering the events in the order they are emitted.
+pub use crate::token::events::SequencedEvent;
+
 /// Event emitted when the token-contract is replaced.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
@@ -25,7 +25,22 @@ extern crate alloc;
 /// Types to interact with the token-contract.
 pub mod token;
 pub use token::account::{Account, AccountInfo};
-pub use token::ZERO_ADDRESS;
+pub use token::attributes::{AccountAttributes, Jurisdiction};
+pub use token::audit::{InvariantReport, InvariantViolation, SupplyBreakdown};
+pub use token::clearing::PendingTransfer;
+pub use token::currency::{CurrencyId, CurrencyMetadata, DEFAULT_CURRENCY};
+pub use token::document::Document;
+pub use token::hold::Hold;
+pub use token::migration::ExportedAccount;
+pub use token::reason::Reason;
+pub use token::redemption::RedemptionRequest;
+pub use token::reserves::ReserveAttestation;
+pub use token::signed_call::SignedCall;
+pub use token::transfer_status::TransferStatus;
+pub use token::{RESERVED_ADDRESSES, ZERO_ADDRESS};
 
 /// Additional types used to interact with the access-control-contract.
 pub mod access_control;
+
+/// Types used to interact with the migration-contract.
+pub mod migrator;
@@ -0,0 +1,14 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+/// Error messages given by migration-contract.
+pub mod error;
+/// Events emitted by the migration-contract.
+pub mod events;
+/// Module for the progress of a migration.
+pub(crate) mod progress;
+
+pub use progress::MigrationProgress;
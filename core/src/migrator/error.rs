@@ -0,0 +1,29 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+// The error messages given by the migration-contract.
+
+/// Error message given when the contract has already been initialized and init
+/// is called.
+pub const ALLREADY_INITIALIZED: &str =
+    "The contract has already been initialized";
+
+/// Error message given when a holder tries to migrate after the migration
+/// window has been closed.
+pub const MIGRATION_CLOSED: &str = "The migration window is closed";
+
+/// Error message given when anyone but the issuer tries to close the
+/// migration window.
+pub const UNAUTHORIZED_ISSUER: &str = "Only the issuer can close the migration";
+
+/// Error message given when a holder tries to migrate a value of zero.
+pub const ZERO_MIGRATION: &str = "The migrated value must not be zero";
+
+/// Error message given when the migrated value didn't arrive in the account of
+/// the migration-contract on the old token-contract, e.g. because the transfer
+/// was queued for clearing.
+pub const LOCK_NOT_SETTLED: &str =
+    "The migrated tokens were not locked in the old token-contract";
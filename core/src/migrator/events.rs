@@ -0,0 +1,45 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// Event emitted when a holder migrated tokens from the old to the new
+/// token-contract.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Migrated {
+    /// The holder that migrated the tokens.
+    pub holder: Account,
+    /// The value locked in the old and minted in the new token-contract.
+    pub value: u64,
+}
+
+impl Migrated {
+    /// Event topic used when a holder migrated tokens.
+    pub const TOPIC: &'static str = "migrated";
+}
+
+/// Event emitted when the issuer closed the migration window.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct MigrationClosed {
+    /// The total value migrated before the window was closed.
+    pub migrated: u64,
+    /// The number of holders that migrated before the window was closed.
+    pub holders: u64,
+}
+
+impl MigrationClosed {
+    /// Event topic used when the migration window has been closed.
+    pub const TOPIC: &'static str = "migration_closed";
+}
@@ -0,0 +1,26 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// The progress of a holder-driven migration between two token-contracts.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+#[allow(clippy::module_name_repetitions)]
+pub struct MigrationProgress {
+    /// The total value locked in the old and minted in the new
+    /// token-contract.
+    pub migrated: u64,
+    /// The number of distinct holders that migrated.
+    pub holders: u64,
+    /// The supply of the old token-contract at the time of the query.
+    pub old_supply: u64,
+    /// Whether holders can still migrate.
+    pub open: bool,
+}
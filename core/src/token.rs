@@ -8,15 +8,60 @@
 pub(crate) mod account;
 use account::Account;
 
+/// Module for the reason given on administrative actions.
+pub(crate) mod reason;
+
+/// Module for the redemption requests of holders.
+pub(crate) mod redemption;
+
+/// Module for the holds on account balances.
+pub(crate) mod hold;
+
+/// Module for the transfers pending clearing.
+pub(crate) mod clearing;
+
+/// Module for the currencies managed by a token-contract.
+pub(crate) mod currency;
+
+/// Module for the state migrated between token-contract versions.
+pub(crate) mod migration;
+
+/// Module for the calls authorized by the signature of an external account.
+pub(crate) mod signed_call;
+
+/// Module for the outcome of transfer preflight checks.
+pub(crate) mod transfer_status;
+
+/// Module for the attributes of accounts.
+pub(crate) mod attributes;
+
+/// Module for the legal documents of the token.
+pub(crate) mod document;
+
+/// Module for the attestations of the reserves backing the token.
+pub(crate) mod reserves;
+
+/// Module for the audit of the supply and the state invariants.
+pub(crate) mod audit;
+
+/// Compact encoding of the events emitted by the token-contract.
+pub mod compact;
+/// Function names of the interface of a compliance contract.
+pub mod compliance;
 /// Error messages given by token-contract panics.
 pub mod error;
 /// Events emitted by the token-contract.
 pub mod events;
+/// Merkle tree committing to the balances of the accounts.
+pub mod merkle;
 
 use dusk_core::abi::{ContractId, CONTRACT_ID_BYTES};
 
 /// Zero address.
-/// TODO: Consider having this in core & make it a reserved address so that no
-/// one can ever use it.
 pub const ZERO_ADDRESS: Account =
     Account::Contract(ContractId::from_bytes([0; CONTRACT_ID_BYTES]));
+
+/// Addresses that can never be credited with tokens or receive the ownership
+/// of a token-contract. Besides these, a token-contract also reserves its own
+/// id and the burn addresses added by its ownership.
+pub const RESERVED_ADDRESSES: [Account; 1] = [ZERO_ADDRESS];
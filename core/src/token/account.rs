@@ -74,6 +74,8 @@ impl Ord for Account {
 pub struct AccountInfo {
     /// The balance of the account.
     pub balance: u64,
+    /// The part of the balance that is on hold and can't be spent.
+    pub held: u64,
     /// Status of the account.
     ///
     /// # Variants
@@ -96,9 +98,23 @@ impl AccountInfo {
     /// An empty account.
     pub const EMPTY: Self = Self {
         balance: 0,
+        held: 0,
         status: 0,
     };
 
+    /// Check if the account holds no tokens and has no status.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::EMPTY
+    }
+
+    /// The part of the balance that is not on hold, or zero if more than the
+    /// balance is on hold.
+    #[must_use]
+    pub fn spendable(&self) -> u64 {
+        self.balance.saturating_sub(self.held)
+    }
+
     /// Check if the account is blocked.
     #[must_use]
     pub fn is_blocked(&self) -> bool {
@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// A jurisdiction, given as an ISO 3166-1 alpha-2 country code, e.g. `*b"DE"`.
+pub type Jurisdiction = [u8; 2];
+
+/// The attributes of an account set by the attribute manager of the
+/// token-contract, that transfer rules can depend on.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+#[allow(clippy::module_name_repetitions)]
+pub struct AccountAttributes {
+    /// The jurisdiction of the holder of the account.
+    pub jurisdiction: Option<Jurisdiction>,
+    /// The investor tier of the holder, as defined by the issuer.
+    pub investor_tier: Option<u8>,
+    /// The risk score of the account, as defined by the issuer.
+    pub risk_score: Option<u8>,
+}
+
+impl AccountAttributes {
+    /// An account without any attributes.
+    pub const EMPTY: Self = Self {
+        jurisdiction: None,
+        investor_tier: None,
+        risk_score: None,
+    };
+
+    /// Check if no attribute is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.jurisdiction.is_none()
+            && self.investor_tier.is_none()
+            && self.risk_score.is_none()
+    }
+}
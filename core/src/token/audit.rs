@@ -0,0 +1,113 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// A breakdown of the supply of the token. The `ownership`, `frozen`,
+/// `blocked`, `escrowed` and `circulating` tokens add up to the `supply`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SupplyBreakdown {
+    /// The total supply.
+    pub supply: u64,
+    /// The total amount ever minted, including the balances of imported
+    /// accounts.
+    pub minted: u128,
+    /// The total amount ever burned, including settled and controller
+    /// redemptions.
+    pub burned: u128,
+    /// The balance of the ownership.
+    pub ownership: u64,
+    /// The balances of frozen accounts, other than the ownership.
+    pub frozen: u64,
+    /// The balances of blocked accounts, other than the ownership.
+    pub blocked: u64,
+    /// The tokens held in escrow by the token-contract for pending
+    /// redemptions and transfers pending clearing.
+    pub escrowed: u64,
+    /// The balances of all other accounts.
+    pub circulating: u64,
+}
+
+/// A violation of an invariant of the token-contract state.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub enum InvariantViolation {
+    /// More of the balance of the account is on hold than the account holds.
+    HeldExceedsBalance {
+        /// The account.
+        account: Account,
+        /// The balance of the account.
+        balance: u64,
+        /// The part of the balance on hold.
+        held: u64,
+    },
+    /// The account has a status other than no status, frozen or blocked.
+    UnknownStatus {
+        /// The account.
+        account: Account,
+        /// The status of the account.
+        status: u64,
+    },
+    /// The account is frozen or blocked, but no sanction reason is recorded
+    /// for it.
+    UnrecordedSanction {
+        /// The account.
+        account: Account,
+    },
+    /// A sanction reason is recorded for an account that is neither frozen
+    /// nor blocked.
+    StaleSanction {
+        /// The account.
+        account: Account,
+    },
+    /// The supply is not the total minted minus the total burned.
+    SupplyMismatch {
+        /// The total supply.
+        supply: u64,
+        /// The total amount ever minted.
+        minted: u128,
+        /// The total amount ever burned.
+        burned: u128,
+    },
+    /// The sum of the balances of all accounts and the escrowed tokens is not
+    /// the supply.
+    BalanceMismatch {
+        /// The total supply.
+        supply: u64,
+        /// The sum of the balances of all accounts.
+        balances: u64,
+        /// The tokens held in escrow by the token-contract.
+        escrowed: u64,
+    },
+}
+
+/// The outcome of checking the invariants of a page of accounts.
+///
+/// The accounts are checked in pages, each continuing after the `last`
+/// account of the previous page with its running sum of `balances`. The
+/// invariants over the whole state are checked once the last page is reached.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct InvariantReport {
+    /// The last account checked, or the account the page started after if
+    /// the page is empty.
+    pub last: Option<Account>,
+    /// The sum of the balances of the accounts checked so far, saturating at
+    /// `u64::MAX`.
+    pub balances: u64,
+    /// Whether all accounts have been checked.
+    pub complete: bool,
+    /// The violations found in the page.
+    pub violations: Vec<InvariantViolation>,
+}
@@ -0,0 +1,35 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// A transfer exceeding the clearing threshold. The tokens are held in escrow
+/// by the token-contract until the transfer is approved or rejected by the
+/// clearing agent, or cancelled by the sender.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PendingTransfer {
+    /// The account the tokens are transferred from.
+    pub sender: Account,
+    /// The account that initiated the transfer on behalf of the sender, if
+    /// any.
+    pub spender: Option<Account>,
+    /// Whether the value was taken from the allowance of the spender, in which
+    /// case it is restored to it if the transfer is rejected or cancelled.
+    /// Operator sends don't spend an allowance.
+    pub from_allowance: bool,
+    /// The account receiving the tokens once the transfer is approved.
+    pub receiver: Account,
+    /// The value held in escrow.
+    pub value: u64,
+    /// The payment reference given by the sender, if any.
+    pub memo: Option<Vec<u8>>,
+}
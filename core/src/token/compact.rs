@@ -0,0 +1,211 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bytes::Serializable;
+use dusk_core::abi::{ContractId, CONTRACT_ID_BYTES};
+use dusk_core::signatures::bls::PublicKey;
+
+use crate::token::events::Transfer;
+use crate::{Account, CurrencyId, Reason, DEFAULT_CURRENCY};
+
+/// The version of the compact encoding, written as the first byte of every
+/// compactly encoded event.
+pub const VERSION: u8 = 2;
+
+const CURRENCY_FLAG: u8 = 1;
+const SPENDER_FLAG: u8 = 1 << 1;
+const REASON_FLAG: u8 = 1 << 2;
+
+const EXTERNAL_TAG: u8 = 0;
+const CONTRACT_TAG: u8 = 1;
+
+/// Error returned when decoding a compactly encoded event fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The event is encoded with an unsupported version of the encoding.
+    UnsupportedVersion(u8),
+    /// The event is shorter or longer than its fields.
+    InvalidLength,
+    /// The event holds an account that is not validly encoded.
+    InvalidAccount,
+}
+
+/// Encode a [`Transfer`] event emitted for the given currency with the given
+/// sequence number, see [`SequencedEvent`].
+///
+/// [`SequencedEvent`]: crate::token::events::SequencedEvent
+///
+/// # Layout
+/// All integers are little-endian.
+///
+/// | Field      | Size             | Present if                       |
+/// |------------|------------------|----------------------------------|
+/// | `version`  | 1                | always, equal to [`VERSION`]     |
+/// | `flags`    | 1                | always                           |
+/// | `sequence` | 8                | always                           |
+/// | `currency` | 8                | `flags & 1`                      |
+/// | `sender`   | account          | always                           |
+/// | `spender`  | account          | `flags & 2`                      |
+/// | `receiver` | account          | always                           |
+/// | `value`    | 8                | always                           |
+/// | `reason`   | 8 + 32           | `flags & 4`                      |
+///
+/// An account is a tag byte, followed by the 96 bytes of the compressed
+/// public key of an external account (tag `0`) or the 32 bytes of the id of a
+/// contract account (tag `1`).
+///
+/// The currency is only written for currencies other than the
+/// [`DEFAULT_CURRENCY`].
+#[must_use]
+pub fn encode_transfer(
+    sequence: u64,
+    currency: CurrencyId,
+    transfer: &Transfer,
+) -> Vec<u8> {
+    let mut flags = 0;
+    if currency != DEFAULT_CURRENCY {
+        flags |= CURRENCY_FLAG;
+    }
+    if transfer.spender.is_some() {
+        flags |= SPENDER_FLAG;
+    }
+    if transfer.reason.is_some() {
+        flags |= REASON_FLAG;
+    }
+
+    let mut bytes = Vec::with_capacity(256);
+    bytes.push(VERSION);
+    bytes.push(flags);
+    bytes.extend_from_slice(&sequence.to_le_bytes());
+    if currency != DEFAULT_CURRENCY {
+        bytes.extend_from_slice(&currency.to_le_bytes());
+    }
+    write_account(&mut bytes, transfer.sender);
+    if let Some(spender) = transfer.spender {
+        write_account(&mut bytes, spender);
+    }
+    write_account(&mut bytes, transfer.receiver);
+    bytes.extend_from_slice(&transfer.value.to_le_bytes());
+    if let Some(reason) = transfer.reason {
+        bytes.extend_from_slice(&reason.code.to_le_bytes());
+        bytes.extend_from_slice(&reason.reference);
+    }
+
+    bytes
+}
+
+/// Decode a [`Transfer`] event encoded with [`encode_transfer`].
+///
+/// Returns the sequence number of the event, the currency it was emitted for
+/// and the event.
+///
+/// # Errors
+/// If the bytes are not a valid encoding of a transfer event.
+#[allow(clippy::similar_names)]
+pub fn decode_transfer(
+    bytes: &[u8],
+) -> Result<(u64, CurrencyId, Transfer), DecodeError> {
+    let mut reader = Reader(bytes);
+
+    let version = reader.u8()?;
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+    let flags = reader.u8()?;
+    let sequence = reader.u64()?;
+
+    let currency = if flags & CURRENCY_FLAG == 0 {
+        DEFAULT_CURRENCY
+    } else {
+        reader.u64()?
+    };
+    let sender = reader.account()?;
+    let spender = if flags & SPENDER_FLAG == 0 {
+        None
+    } else {
+        Some(reader.account()?)
+    };
+    let receiver = reader.account()?;
+    let value = reader.u64()?;
+    let reason = if flags & REASON_FLAG == 0 {
+        None
+    } else {
+        let code = reader.u64()?;
+        let reference = reader.array()?;
+        Some(Reason::new(code, reference))
+    };
+
+    if !reader.0.is_empty() {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let transfer = Transfer {
+        sender,
+        spender,
+        receiver,
+        value,
+        reason,
+    };
+    Ok((sequence, currency, transfer))
+}
+
+/// Write an account in the compact encoding.
+pub(crate) fn write_account(bytes: &mut Vec<u8>, account: Account) {
+    match account {
+        Account::External(pk) => {
+            bytes.push(EXTERNAL_TAG);
+            bytes.extend_from_slice(&pk.to_bytes());
+        }
+        Account::Contract(contract) => {
+            bytes.push(CONTRACT_TAG);
+            bytes.extend_from_slice(contract.as_bytes());
+        }
+    }
+}
+
+/// Reads the fields of an encoded event in order.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        if self.0.len() < N {
+            return Err(DecodeError::InvalidLength);
+        }
+        let (array, rest) = self.0.split_at(N);
+        self.0 = rest;
+
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(array);
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        let [byte] = self.array()?;
+        Ok(byte)
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn account(&mut self) -> Result<Account, DecodeError> {
+        match self.u8()? {
+            EXTERNAL_TAG => {
+                let bytes = self.array::<{ PublicKey::SIZE }>()?;
+                let pk = PublicKey::from_bytes(&bytes)
+                    .map_err(|_| DecodeError::InvalidAccount)?;
+                Ok(Account::External(pk))
+            }
+            CONTRACT_TAG => {
+                let bytes = self.array::<CONTRACT_ID_BYTES>()?;
+                Ok(Account::Contract(ContractId::from_bytes(bytes)))
+            }
+            _ => Err(DecodeError::InvalidAccount),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The interface a compliance contract registered with a token-contract has
+//! to implement.
+//!
+//! Both functions take the `(sender, receiver, value)` of the operation as
+//! `(Account, Account, u64)`. Mints are passed with the [`ZERO_ADDRESS`] as
+//! the sender and burns with the [`ZERO_ADDRESS`] as the receiver.
+//!
+//! Redemptions are checked as burns when they are requested and notified
+//! when they are settled, and executed holds are checked and notified as
+//! transfers from the payer to the payee.
+//!
+//! # Administrative actions
+//!
+//! `force_transfer` and `controller_redeem` are not checked, since they are
+//! the means of the ownership to act on accounts regardless of their status,
+//! including accounts the compliance contract restricts. The compliance
+//! contract is still notified of them. Importing the state of a previous
+//! token-contract is neither checked nor notified.
+//!
+//! [`ZERO_ADDRESS`]: crate::ZERO_ADDRESS
+
+/// The function called before every transfer, mint and burn, except for the
+/// administrative actions. It returns a `bool`, and the operation is reverted
+/// when it returns `false` or panics.
+pub const CAN_TRANSFER: &str = "can_transfer";
+
+/// The function called after every executed transfer, mint and burn, so the
+/// compliance contract can update its own state.
+pub const TRANSFERRED: &str = "transferred";
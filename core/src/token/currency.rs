@@ -0,0 +1,31 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::string::String;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// The id of a currency managed by a token-contract.
+#[allow(clippy::module_name_repetitions)]
+pub type CurrencyId = u64;
+
+/// The currency served by the single-currency entrypoints of a
+/// token-contract. Its events are emitted without a currency id.
+pub const DEFAULT_CURRENCY: CurrencyId = 0;
+
+/// The metadata of a currency managed by a token-contract.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[allow(clippy::module_name_repetitions)]
+pub struct CurrencyMetadata {
+    /// The name of the currency.
+    pub name: String,
+    /// The symbol of the currency.
+    pub symbol: String,
+    /// The decimals of the currency.
+    pub decimals: u8,
+}
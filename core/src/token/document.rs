@@ -0,0 +1,30 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::string::String;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// A legal document of the token, such as the white paper or the terms,
+/// registered by the ownership under a name.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Document {
+    /// The location of the document.
+    pub uri: String,
+    /// The hash of the content of the document.
+    pub hash: [u8; 32],
+    /// The block height at which the document was last set.
+    pub modified: u64,
+}
+
+impl Document {
+    /// The maximum size of a document name in bytes.
+    pub const MAX_NAME_SIZE: usize = 64;
+    /// The maximum size of a document URI in bytes.
+    pub const MAX_URI_SIZE: usize = 256;
+}
@@ -11,7 +11,31 @@ pub const BALANCE_TOO_LOW: &str = "The account doesn't have enough tokens";
 /// Error message for when the account is not found in the contract.
 pub const ACCOUNT_NOT_FOUND: &str = "The account does not exist";
 
-/// Shielded transactions are not supported.
+/// Error message for when an account without allowances is spent from.
+pub const NO_ALLOWANCES: &str = "The account has no allowances";
+
+/// Error message for when a spender spends from an account that didn't approve
+/// it.
+pub const SPENDER_NOT_ALLOWED: &str =
+    "The spender is not allowed to use the account";
+
+/// Error message for when a spender spends more than its allowance.
+pub const ALLOWANCE_TOO_LOW: &str =
+    "The spender can't spent the defined amount";
+
+/// Error message for when transfers between the jurisdictions of the sender
+/// and the receiver are restricted.
+pub const JURISDICTION_RESTRICTED: &str =
+    "Transfers between the jurisdictions are restricted";
+
+/// Error message for when a spender is not an operator of the account.
+pub const NOT_OPERATOR: &str = "The spender is not an operator of the account";
+
+/// Error message for when the compliance contract vetoes an operation.
+pub const COMPLIANCE_REJECTED: &str =
+    "The operation is rejected by the compliance contract";
+
+/// Shielded transactions are only supported through signed calls.
 pub const SHIELDED_NOT_SUPPORTED: &str =
     "Shielded transactions are not supported";
 
@@ -33,3 +57,81 @@ pub const FROZEN: &str = "Account is frozen";
 
 /// Error message for when the contract is paused.
 pub const PAUSED_MESSAGE: &str = "Contract is paused";
+
+/// Error message for when the contract is unpaused while not paused.
+pub const NOT_PAUSED: &str = "Contract is not paused";
+
+/// Error message for when a reserved address is credited or set as the
+/// ownership.
+pub const RESERVED_ADDRESS: &str = "The account is a reserved address";
+
+/// Error message for when a currency is not managed by the contract.
+pub const CURRENCY_NOT_FOUND: &str = "The currency does not exist";
+
+/// Error message for when the state is imported after the import has been
+/// finalized.
+pub const IMPORT_FINALIZED: &str = "The state import is finalized";
+
+/// Error message for when an imported account has an unknown status, or a
+/// sanction reason that doesn't match its status.
+pub const INVALID_IMPORTED_STATUS: &str =
+    "The imported status is unknown or doesn't match the sanction reason";
+
+/// Error message for when a hold is not found in the contract.
+pub const HOLD_NOT_FOUND: &str = "The hold does not exist";
+
+/// Error message for when a pending transfer is not found in the contract.
+pub const PENDING_TRANSFER_NOT_FOUND: &str =
+    "The pending transfer does not exist";
+
+/// Error message for when a transfer exceeding the clearing threshold can not
+/// be queued.
+pub const CLEARING_REQUIRED: &str = "The transfer requires clearing";
+
+/// Error message for when the memo of a transfer is too large.
+pub const MEMO_TOO_LARGE: &str = "The memo exceeds the maximum size";
+
+/// Error message for when minting is gated by reserve attestations but none
+/// has been published.
+pub const ATTESTATION_NOT_FOUND: &str = "No reserve attestation was published";
+
+/// Error message for when the latest reserve attestation is older than the
+/// configured validity.
+pub const ATTESTATION_STALE: &str = "The reserve attestation is stale";
+
+/// Error message for when a reserve attestation is not more recent than the
+/// latest one.
+pub const ATTESTATION_OUTDATED: &str =
+    "The reserve attestation is older than the latest one";
+
+/// Error message for when a mint would push the supply above the attested
+/// reserves.
+pub const RESERVES_EXCEEDED: &str = "The supply would exceed the reserves";
+
+/// Error message for when a document is not registered in the contract.
+pub const DOCUMENT_NOT_FOUND: &str = "The document does not exist";
+
+/// Error message for when the name or the URI of a document is too large.
+pub const DOCUMENT_TOO_LARGE: &str =
+    "The document name or URI exceeds the maximum size";
+
+/// Error message for when a redemption request is not found in the contract.
+pub const REDEMPTION_NOT_FOUND: &str = "The redemption request does not exist";
+
+/// Error message for when the signature of a signed call is not valid for the
+/// signer's current nonce.
+pub const INVALID_SIGNATURE: &str = "The signature of the call is invalid";
+
+/// Error message for when a function can not be executed as a signed call.
+pub const SIGNED_CALL_NOT_SUPPORTED: &str =
+    "The function can't be executed as a signed call";
+
+/// Error message for when the arguments of a signed call don't match the
+/// called function.
+pub const INVALID_SIGNED_CALL_ARGUMENTS: &str =
+    "The arguments of the signed call are invalid";
+
+/// Error message for when a function that modifies balances, allowances or
+/// operators is called while the receiver of a `transfer_and_call` is called.
+pub const REENTRANT_CALL: &str =
+    "The token-contract can't be modified during a transfer_and_call";
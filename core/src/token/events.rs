@@ -4,10 +4,62 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use bytecheck::CheckBytes;
+use dusk_core::abi::ContractId;
 use rkyv::{Archive, Deserialize, Serialize};
 
-use crate::Account;
+use crate::{
+    Account, AccountAttributes, CurrencyId, CurrencyMetadata, Document,
+    Jurisdiction, Reason, ReserveAttestation,
+};
+
+/// Wrapper of every event emitted by the token-contract, numbering the events
+/// in the order they are emitted, across all currencies.
+///
+/// The sequence numbers start at `1` and increase by one with every event, so
+/// a gap in the numbers reveals a missed event.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SequencedEvent<E> {
+    /// The sequence number of the event.
+    pub sequence: u64,
+    /// The event.
+    pub event: E,
+}
+
+/// Wrapper of the events emitted for a currency other than the
+/// [`DEFAULT_CURRENCY`]. The events of the default currency are emitted
+/// unwrapped, under the same topics.
+///
+/// [`DEFAULT_CURRENCY`]: crate::DEFAULT_CURRENCY
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct CurrencyEvent<E> {
+    /// The currency the event was emitted for.
+    pub currency: CurrencyId,
+    /// The event.
+    pub event: E,
+}
+
+/// Event emitted when a new currency is created.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct CurrencyCreated {
+    /// The id of the currency.
+    pub currency: CurrencyId,
+    /// The metadata of the currency.
+    pub metadata: CurrencyMetadata,
+    /// The ownership of the currency.
+    pub ownership: Account,
+}
+
+impl CurrencyCreated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "currency_created";
+}
 
 /// Event emitted when tokens are transferred from one account to another.
 #[derive(
@@ -23,17 +75,51 @@ pub struct Transfer {
     pub receiver: Account,
     /// The value transferred.
     pub value: u64,
+    /// The reason given by the ownership, set if the transfer is the result of
+    /// an administrative action, i.e. `mint`, `burn`, `force_transfer` or
+    /// `controller_redeem`.
+    pub reason: Option<Reason>,
 }
 
 impl Transfer {
     /// Event topic used when a normal transfer is made.
     pub const TRANSFER_TOPIC: &'static str = "transfer";
+    /// Event topic used when an operator sends tokens of a holder.
+    pub const OPERATOR_SEND_TOPIC: &'static str = "operator_send";
     /// Event topic used when a forced transfer is made.
     pub const FORCE_TRANSFER_TOPIC: &'static str = "force_transfer";
     /// Event topic used when new tokens are minted.
     pub const MINT_TOPIC: &'static str = "mint";
     /// Event topic used when tokens are burned.
     pub const BURN_TOPIC: &'static str = "burn";
+    /// Event topic used when tokens are burned directly from an account by
+    /// the ownership.
+    pub const CONTROLLER_REDEEM_TOPIC: &'static str = "controller_redeem";
+}
+
+/// Event emitted next to a [`Transfer`] event when the transfer carries a
+/// payment reference, e.g. an ISO 20022 end-to-end id.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferReference {
+    /// The account the tokens are transferred from.
+    pub sender: Account,
+    /// The account that initiated the transfer on behalf of the sender, if
+    /// any.
+    pub spender: Option<Account>,
+    /// The account receiving the tokens.
+    pub receiver: Account,
+    /// The value transferred.
+    pub value: u64,
+    /// The payment reference given by the sender.
+    pub memo: Vec<u8>,
+}
+
+impl TransferReference {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "transfer_reference";
+    /// The maximum size of a memo in bytes.
+    pub const MAX_MEMO_SIZE: usize = 140;
 }
 
 /// Event emitted when a spender is approved on an account.
@@ -55,6 +141,26 @@ impl Approve {
     pub const APPROVE_TOPIC: &'static str = "approve";
 }
 
+/// Event emitted when a holder authorizes or revokes an operator, allowed to
+/// send the holder's full balance.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct OperatorUpdated {
+    /// The holder of the tokens.
+    pub holder: Account,
+    /// The affected operator.
+    pub operator: Account,
+}
+
+impl OperatorUpdated {
+    /// The topic of the event when an operator is authorized.
+    pub const AUTHORIZED_TOPIC: &'static str = "operator_authorized";
+    /// The topic of the event when an operator is revoked.
+    pub const REVOKED_TOPIC: &'static str = "operator_revoked";
+}
+
 /// Event emitted when a contract is paused or unpaused.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
@@ -70,6 +176,24 @@ impl PauseToggled {
     pub const TOPIC: &'static str = "pause_toggled";
 }
 
+/// Event emitted when the ownership adds or removes a guardian, allowed to
+/// pause the token-contract.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct GuardianUpdated {
+    /// The affected guardian.
+    pub guardian: Account,
+}
+
+impl GuardianUpdated {
+    /// The topic of the event when a guardian is added.
+    pub const ADDED_TOPIC: &'static str = "guardian_added";
+    /// The topic of the event when a guardian is removed.
+    pub const REMOVED_TOPIC: &'static str = "guardian_removed";
+}
+
 /// Event emitted when the ownership of a contract is transferred.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
@@ -107,6 +231,22 @@ impl OwnershipAccepted {
     pub const TOPIC: &'static str = "ownership_accepted";
 }
 
+/// Event emitted when the ownership adds a burn address to the reserved
+/// addresses of a token-contract.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ReservedAddressAdded {
+    /// The account that is now reserved.
+    pub account: Account,
+}
+
+impl ReservedAddressAdded {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "reserved_address_added";
+}
+
 /// Event emitted when an account status changes.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
@@ -117,6 +257,8 @@ pub struct AccountStatus {
     pub account: Account,
     /// The new status of the account.
     pub status: u64,
+    /// The reason given by the ownership for the status change.
+    pub reason: Reason,
 }
 
 impl AccountStatus {
@@ -130,34 +272,401 @@ impl AccountStatus {
     pub const UNFROZEN_TOPIC: &'static str = "unfrozen";
 
     /// Create a new `AccountStatus` event for a blocked account.
-    pub fn blocked(account: impl Into<Account>) -> Self {
+    pub fn blocked(account: impl Into<Account>, reason: Reason) -> Self {
         Self {
             account: account.into(),
             status: 2,
+            reason,
         }
     }
 
     /// Create a new `AccountStatus` event for an unblocked account.
-    pub fn unblocked(account: impl Into<Account>) -> Self {
+    pub fn unblocked(account: impl Into<Account>, reason: Reason) -> Self {
         Self {
             account: account.into(),
             status: 0,
+            reason,
         }
     }
 
     /// Create a new `AccountStatus` event for a frozen account.
-    pub fn frozen(account: impl Into<Account>) -> Self {
+    pub fn frozen(account: impl Into<Account>, reason: Reason) -> Self {
         Self {
             account: account.into(),
             status: 1,
+            reason,
         }
     }
 
     /// Create a new `AccountStatus` event for an unfrozen account.
-    pub fn unfrozen(account: impl Into<Account>) -> Self {
+    pub fn unfrozen(account: impl Into<Account>, reason: Reason) -> Self {
         Self {
             account: account.into(),
             status: 0,
+            reason,
         }
     }
 }
+
+/// Event emitted when a holder requests the redemption of tokens.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RedemptionRequested {
+    /// The id of the redemption request.
+    pub id: u64,
+    /// The account requesting the redemption.
+    pub holder: Account,
+    /// The value held in escrow.
+    pub value: u64,
+    /// A reference to the payout instructions of the holder.
+    pub payout_reference: [u8; 32],
+}
+
+impl RedemptionRequested {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "redemption_requested";
+}
+
+/// Event emitted when a redemption request is settled and the escrowed tokens
+/// are burned.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RedemptionSettled {
+    /// The id of the redemption request.
+    pub id: u64,
+    /// The account that requested the redemption.
+    pub holder: Account,
+    /// The value burned.
+    pub value: u64,
+    /// A reference to the payout made by the issuer, e.g. the id of the bank
+    /// transfer.
+    pub settlement_reference: [u8; 32],
+}
+
+impl RedemptionSettled {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "redemption_settled";
+}
+
+/// Event emitted when a redemption request is rejected and the escrowed
+/// tokens are returned to the holder.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RedemptionRejected {
+    /// The id of the redemption request.
+    pub id: u64,
+    /// The account that requested the redemption.
+    pub holder: Account,
+    /// The value returned to the holder.
+    pub value: u64,
+    /// The reason given by the ownership for the rejection.
+    pub reason: Reason,
+}
+
+impl RedemptionRejected {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "redemption_rejected";
+}
+
+/// Event emitted when a part of an account's balance is put on hold.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct HoldCreated {
+    /// The id of the hold.
+    pub id: u64,
+    /// The account whose balance is on hold.
+    pub payer: Account,
+    /// The account receiving the tokens when the hold is executed.
+    pub payee: Account,
+    /// The account allowed to execute or release the hold.
+    pub notary: Account,
+    /// The value on hold.
+    pub value: u64,
+    /// The block height after which the hold expires, 0 if it never does.
+    pub expiration: u64,
+}
+
+impl HoldCreated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "hold_created";
+}
+
+/// Event emitted when a hold is executed and the held tokens are transferred
+/// to the payee.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct HoldExecuted {
+    /// The id of the hold.
+    pub id: u64,
+    /// The account the tokens are transferred from.
+    pub payer: Account,
+    /// The account receiving the tokens.
+    pub payee: Account,
+    /// The value transferred, the rest of the hold is released.
+    pub value: u64,
+}
+
+impl HoldExecuted {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "hold_executed";
+}
+
+/// Event emitted when a hold is released and the held tokens become spendable
+/// again.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct HoldReleased {
+    /// The id of the hold.
+    pub id: u64,
+    /// The account whose balance was on hold.
+    pub payer: Account,
+    /// The value released.
+    pub value: u64,
+}
+
+impl HoldReleased {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "hold_released";
+}
+
+/// Event emitted when a transfer exceeds the clearing threshold and is queued
+/// for clearing.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferQueued {
+    /// The id of the pending transfer.
+    pub id: u64,
+    /// The account the tokens are transferred from.
+    pub sender: Account,
+    /// The account that initiated the transfer on behalf of the sender, if
+    /// any.
+    pub spender: Option<Account>,
+    /// The account receiving the tokens once the transfer is approved.
+    pub receiver: Account,
+    /// The value held in escrow.
+    pub value: u64,
+}
+
+impl TransferQueued {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "transfer_queued";
+}
+
+/// Event emitted when a pending transfer leaves the clearing queue.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferCleared {
+    /// The id of the pending transfer.
+    pub id: u64,
+    /// The account the tokens are transferred from.
+    pub sender: Account,
+    /// The account receiving the tokens if the transfer is approved.
+    pub receiver: Account,
+    /// The value of the pending transfer.
+    pub value: u64,
+    /// The reason given by the clearing agent, `None` if the transfer is
+    /// approved or cancelled by the sender.
+    pub reason: Option<Reason>,
+}
+
+impl TransferCleared {
+    /// The topic of the event when the transfer is approved and the tokens
+    /// are credited to the receiver.
+    pub const APPROVED_TOPIC: &'static str = "transfer_approved";
+    /// The topic of the event when the transfer is rejected and the tokens
+    /// are returned to the sender.
+    pub const REJECTED_TOPIC: &'static str = "transfer_rejected";
+    /// The topic of the event when the transfer is cancelled by the sender
+    /// and the tokens are returned.
+    pub const CANCELLED_TOPIC: &'static str = "transfer_cancelled";
+}
+
+/// Event emitted when the clearing configuration of the token changes.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ClearingUpdated {
+    /// The value above which transfers are queued, `None` if clearing is
+    /// disabled.
+    pub threshold: Option<u64>,
+    /// The account allowed to approve and reject pending transfers besides
+    /// the ownership.
+    pub agent: Option<Account>,
+}
+
+impl ClearingUpdated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "clearing_updated";
+}
+
+/// Event emitted when the attributes of an account change.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct AttributesUpdated {
+    /// The affected account.
+    pub account: Account,
+    /// The new attributes of the account.
+    pub attributes: AccountAttributes,
+}
+
+impl AttributesUpdated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "attributes_updated";
+}
+
+/// Event emitted when the attribute manager of the token changes.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct AttributeManagerUpdated {
+    /// The account allowed to set account attributes besides the ownership.
+    pub manager: Option<Account>,
+}
+
+impl AttributeManagerUpdated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "attribute_manager_updated";
+}
+
+/// Event emitted when transfers between two jurisdictions are restricted or
+/// allowed again.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct JurisdictionRestriction {
+    /// The jurisdiction of the sender.
+    pub sender: Jurisdiction,
+    /// The jurisdiction of the receiver.
+    pub receiver: Jurisdiction,
+}
+
+impl JurisdictionRestriction {
+    /// The topic of the event when transfers are restricted.
+    pub const RESTRICTED_TOPIC: &'static str = "jurisdiction_restricted";
+    /// The topic of the event when transfers are allowed again.
+    pub const UNRESTRICTED_TOPIC: &'static str = "jurisdiction_unrestricted";
+}
+
+/// Event emitted when a document of the token is set or removed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct DocumentUpdated {
+    /// The name of the document.
+    pub name: String,
+    /// The document as set, or as it was before being removed.
+    pub document: Document,
+}
+
+impl DocumentUpdated {
+    /// The topic of the event when a document is set.
+    pub const TOPIC: &'static str = "document_updated";
+    /// The topic of the event when a document is removed.
+    pub const REMOVED_TOPIC: &'static str = "document_removed";
+}
+
+/// Event emitted when the attestor publishes a reserve attestation.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ReservesAttested {
+    /// The attestor publishing the attestation.
+    pub attestor: Account,
+    /// The published attestation.
+    pub attestation: ReserveAttestation,
+}
+
+impl ReservesAttested {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "reserves_attested";
+}
+
+/// Event emitted when the minter of the token changes.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct MinterUpdated {
+    /// The account allowed to mint besides the ownership.
+    pub minter: Option<Account>,
+}
+
+impl MinterUpdated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "minter_updated";
+}
+
+/// Event emitted when the attestor configuration of the token changes.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct AttestorUpdated {
+    /// The account publishing reserve attestations, `None` if minting is not
+    /// gated by reserve attestations.
+    pub attestor: Option<Account>,
+    /// The number of blocks after which an attestation is stale and blocks
+    /// minting, `None` if attestations never become stale.
+    pub validity: Option<u64>,
+}
+
+impl AttestorUpdated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "attestor_updated";
+}
+
+/// Event emitted when the compliance contract of the token changes.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ComplianceUpdated {
+    /// The contract checking transfers, mints and burns, `None` if no
+    /// compliance contract is registered.
+    pub compliance: Option<ContractId>,
+}
+
+impl ComplianceUpdated {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "compliance_updated";
+}
+
+/// Event emitted when the import of the state of a previous token-contract is
+/// finalized.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ImportFinalized {
+    /// The number of accounts imported.
+    pub accounts: u64,
+    /// The imported supply, matching the supply of the previous
+    /// token-contract.
+    pub supply: u64,
+}
+
+impl ImportFinalized {
+    /// The topic of the event.
+    pub const TOPIC: &'static str = "import_finalized";
+}
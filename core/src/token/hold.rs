@@ -0,0 +1,40 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// A part of the payer's balance earmarked for a payee. The held value can
+/// not be spent by the payer until the hold is either executed by the notary
+/// or released.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Hold {
+    /// The account whose balance is on hold.
+    pub payer: Account,
+    /// The account receiving the tokens when the hold is executed.
+    pub payee: Account,
+    /// The account allowed to execute or release the hold.
+    pub notary: Account,
+    /// The value on hold.
+    pub value: u64,
+    /// The block height after which the hold can no longer be executed and
+    /// can be released by the payer. A value of 0 means the hold never
+    /// expires.
+    pub expiration: u64,
+}
+
+impl Hold {
+    /// Check if the hold is expired at the given block height.
+    #[must_use]
+    pub fn is_expired(&self, block_height: u64) -> bool {
+        self.expiration != 0 && block_height > self.expiration
+    }
+}
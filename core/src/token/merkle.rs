@@ -0,0 +1,83 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use dusk_core::BlsScalar;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::token::compact::write_account;
+use crate::{Account, AccountInfo};
+
+/// The height of the binary Merkle tree over the balances of the accounts.
+pub const HEIGHT: usize = 32;
+
+/// The value of an empty subtree, and the root of the tree without accounts.
+pub const EMPTY_SUBTREE: [u8; 32] = [0; 32];
+
+/// An inclusion proof of the entry of an account in the Merkle tree over the
+/// balances of the accounts.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct BalanceProof {
+    /// The entry of the account.
+    pub account_info: AccountInfo,
+    /// The position of the leaf of the account in the tree.
+    pub position: u64,
+    /// The siblings on the path from the leaf to the root, starting with the
+    /// sibling of the leaf.
+    pub branch: [[u8; 32]; HEIGHT],
+}
+
+impl BalanceProof {
+    /// Verify that the proof shows the [`AccountInfo`] of the proof to be the
+    /// entry of the given account in the tree with the given `root`.
+    #[must_use]
+    pub fn verify(&self, root: &[u8; 32], account: Account) -> bool {
+        let mut node = leaf(account, &self.account_info);
+        let mut position = self.position;
+
+        for sibling in &self.branch {
+            node = if position & 1 == 0 {
+                self::node(&node, sibling)
+            } else {
+                self::node(sibling, &node)
+            };
+            position >>= 1;
+        }
+
+        position == 0 && node == *root
+    }
+}
+
+/// Return the bytes hashed into the leaf of an account: the account in the
+/// [`compact`](crate::token::compact) encoding, followed by the balance, the
+/// balance on hold and the status as little-endian integers.
+#[must_use]
+pub fn leaf_preimage(account: Account, account_info: &AccountInfo) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(128);
+    write_account(&mut bytes, account);
+    bytes.extend_from_slice(&account_info.balance.to_le_bytes());
+    bytes.extend_from_slice(&account_info.held.to_le_bytes());
+    bytes.extend_from_slice(&account_info.status.to_le_bytes());
+    bytes
+}
+
+/// Compute the leaf of an account.
+#[must_use]
+pub fn leaf(account: Account, account_info: &AccountInfo) -> [u8; 32] {
+    BlsScalar::hash_to_scalar(&leaf_preimage(account, account_info)).to_bytes()
+}
+
+/// Compute the node over two children, hashing their concatenation.
+#[must_use]
+pub fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut bytes = [0; 64];
+    bytes[..32].copy_from_slice(left);
+    bytes[32..].copy_from_slice(right);
+    BlsScalar::hash_to_scalar(&bytes).to_bytes()
+}
@@ -0,0 +1,31 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Reason};
+
+/// The state of an account as exported by a token-contract, to be imported
+/// by a new version of the token-contract.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ExportedAccount {
+    /// The exported account.
+    pub account: Account,
+    /// The balance of the account.
+    pub balance: u64,
+    /// The status of the account.
+    pub status: u64,
+    /// The reason given for the status of the account, if any.
+    pub sanction_reason: Option<Reason>,
+    /// The allowances granted by the account to spenders.
+    pub allowances: Vec<(Account, u64)>,
+    /// The operators authorized by the account.
+    pub operators: Vec<Account>,
+}
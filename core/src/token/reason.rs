@@ -0,0 +1,30 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// The justification given by the ownership for an administrative action on
+/// the token-contract.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Reason {
+    /// The reason code, as defined by the issuer's compliance policy.
+    pub code: u64,
+    /// A reference to the legal basis of the action, e.g. the hash of a
+    /// court order or a ticket ID.
+    pub reference: [u8; 32],
+}
+
+impl Reason {
+    /// Create a new reason from a code and a reference.
+    #[must_use]
+    pub const fn new(code: u64, reference: [u8; 32]) -> Self {
+        Self { code, reference }
+    }
+}
@@ -0,0 +1,28 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// A request of a holder to redeem tokens at par. The tokens are held in
+/// escrow by the token-contract until the request is either settled or
+/// rejected.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+#[allow(clippy::module_name_repetitions)]
+pub struct RedemptionRequest {
+    /// The account requesting the redemption.
+    pub holder: Account,
+    /// The value held in escrow.
+    pub value: u64,
+    /// A reference to the payout instructions of the holder, e.g. the hash of
+    /// the bank account details.
+    pub payout_reference: [u8; 32],
+}
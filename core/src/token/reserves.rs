@@ -0,0 +1,36 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// An attestation of the reserves backing the token, published by the
+/// attestor of the token-contract. While an attestor is set, the supply can
+/// not be minted above the reserves of the latest attestation.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ReserveAttestation {
+    /// The attested reserves, in units of the token.
+    pub reserves: u64,
+    /// The time at which the reserves were attested, as a unix timestamp in
+    /// seconds.
+    pub timestamp: u64,
+    /// The hash of the auditor report the attestation is based on.
+    pub report_hash: [u8; 32],
+    /// The block height at which the attestation was published.
+    pub block_height: u64,
+}
+
+impl ReserveAttestation {
+    /// Check if the attestation is stale at the given block height, given
+    /// the number of blocks an attestation stays valid for.
+    #[must_use]
+    pub fn is_stale(&self, block_height: u64, validity: u64) -> bool {
+        block_height.saturating_sub(self.block_height) > validity
+    }
+}
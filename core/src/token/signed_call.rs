@@ -0,0 +1,101 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use dusk_core::abi::{ContractId, CONTRACT_ID_BYTES};
+use dusk_core::signatures::bls::{PublicKey, SecretKey, Signature};
+
+use crate::CurrencyId;
+
+/// A call to the token-contract that is authorized by the signature of an
+/// external account instead of the public sender of the transaction.
+///
+/// This allows external accounts to use the token-contract from transactions
+/// that don't have a public sender, e.g. transactions paying their fees with
+/// shielded (Phoenix) notes.
+#[derive(Debug, Clone, PartialEq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SignedCall {
+    /// The key of the external account the call is executed for.
+    pub signer: PublicKey,
+    /// The name of the called token-contract function.
+    pub fn_name: String,
+    /// The serialized arguments of the called token-contract function.
+    pub fn_args: Vec<u8>,
+    /// The signature of the signer over the signature message of the call.
+    pub signature: Signature,
+}
+
+impl SignedCall {
+    /// Create a new call signed by the given secret key for the current
+    /// `nonce` of the signer in the given token-contract and currency, on the
+    /// chain with the given `chain_id`.
+    #[must_use]
+    pub fn new(
+        sk: &SecretKey,
+        chain_id: u8,
+        contract: &ContractId,
+        currency: CurrencyId,
+        nonce: u64,
+        fn_name: impl Into<String>,
+        fn_args: Vec<u8>,
+    ) -> Self {
+        let fn_name = fn_name.into();
+        let sig_msg = Self::signature_message(
+            chain_id, contract, currency, nonce, &fn_name, &fn_args,
+        );
+
+        Self {
+            signer: PublicKey::from(sk),
+            fn_name,
+            fn_args,
+            signature: sk.sign(&sig_msg),
+        }
+    }
+
+    /// The signature message of a signed call is the chain id, appended by the
+    /// token-contract's `ContractId`, the currency and the nonce of the signer
+    /// in big endian, the length of the function name as a `u64` in big
+    /// endian, the function name as bytes and the serialized function
+    /// arguments.
+    ///
+    /// The length prefix keeps a call from being resubmitted as a call to
+    /// another function whose name is a prefix of the signed one, e.g.
+    /// `transfer_from` as `transfer` with the arguments shifted by `_from`.
+    #[must_use]
+    pub fn signature_message(
+        chain_id: u8,
+        contract: &ContractId,
+        currency: CurrencyId,
+        nonce: u64,
+        fn_name: &str,
+        fn_args: impl AsRef<[u8]>,
+    ) -> Vec<u8> {
+        let fn_args = fn_args.as_ref();
+        let fn_name_bytes = fn_name.as_bytes();
+        let mut sig_msg = Vec::with_capacity(
+            1 + CONTRACT_ID_BYTES
+                + 3 * size_of::<u64>()
+                + fn_name_bytes.len()
+                + fn_args.len(),
+        );
+        sig_msg.push(chain_id);
+        sig_msg.extend(&contract.to_bytes());
+        sig_msg.extend(&currency.to_be_bytes());
+        sig_msg.extend(&nonce.to_be_bytes());
+        sig_msg.extend(&(fn_name_bytes.len() as u64).to_be_bytes());
+        sig_msg.extend(fn_name_bytes);
+        sig_msg.extend(fn_args);
+
+        sig_msg
+    }
+}
@@ -0,0 +1,88 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use super::error;
+
+/// The outcome of a transfer as determined by the checks of the
+/// token-contract, without executing it.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub enum TransferStatus {
+    /// The transfer would be executed.
+    Allowed,
+    /// The transfer would be queued for clearing.
+    RequiresClearing,
+    /// The token-contract is paused.
+    Paused,
+    /// The receiver is a reserved address.
+    ReservedAddress,
+    /// The account of the sender does not exist.
+    AccountNotFound,
+    /// The sender is blocked.
+    SenderBlocked,
+    /// The sender is frozen.
+    SenderFrozen,
+    /// The receiver is blocked.
+    ReceiverBlocked,
+    /// The spender is blocked.
+    SpenderBlocked,
+    /// The spender is frozen.
+    SpenderFrozen,
+    /// The spendable balance of the sender is lower than the value.
+    BalanceTooLow,
+    /// The sender didn't approve any spenders.
+    NoAllowances,
+    /// The sender didn't approve the spender.
+    SpenderNotAllowed,
+    /// The allowance of the spender is lower than the value.
+    AllowanceTooLow,
+    /// The spender is not an operator of the sender.
+    NotOperator,
+    /// Transfers between the jurisdictions of the sender and the receiver are
+    /// restricted.
+    JurisdictionRestricted,
+    /// The compliance contract vetoes the transfer.
+    ComplianceRejected,
+}
+
+impl TransferStatus {
+    /// Whether the transfer would succeed, either by being executed or by
+    /// being queued for clearing.
+    #[must_use]
+    pub const fn is_allowed(self) -> bool {
+        matches!(self, Self::Allowed | Self::RequiresClearing)
+    }
+
+    /// The error message the token-contract panics with when the transfer is
+    /// executed, or `None` if the transfer would succeed.
+    #[must_use]
+    pub const fn error(self) -> Option<&'static str> {
+        match self {
+            Self::Allowed | Self::RequiresClearing => None,
+            Self::Paused => Some(error::PAUSED_MESSAGE),
+            Self::ReservedAddress => Some(error::RESERVED_ADDRESS),
+            Self::AccountNotFound => Some(error::ACCOUNT_NOT_FOUND),
+            Self::SenderBlocked
+            | Self::ReceiverBlocked
+            | Self::SpenderBlocked => Some(error::BLOCKED),
+            Self::SenderFrozen | Self::SpenderFrozen => Some(error::FROZEN),
+            Self::BalanceTooLow => Some(error::BALANCE_TOO_LOW),
+            Self::NoAllowances => Some(error::NO_ALLOWANCES),
+            Self::SpenderNotAllowed => Some(error::SPENDER_NOT_ALLOWED),
+            Self::AllowanceTooLow => Some(error::ALLOWANCE_TOO_LOW),
+            Self::NotOperator => Some(error::NOT_OPERATOR),
+            Self::JurisdictionRestricted => {
+                Some(error::JURISDICTION_RESTRICTED)
+            }
+            Self::ComplianceRejected => Some(error::COMPLIANCE_REJECTED),
+        }
+    }
+}
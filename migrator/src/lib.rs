@@ -0,0 +1,82 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The migration-contract for moving holders from one version of an electronic
+//! money token to another.
+//!
+//! Holders opt in to the migration themselves: the migrated value is locked in
+//! this contract's account of the old token-contract and the same value is
+//! minted to the holder on the new token-contract. For this to work the holder
+//! needs to approve this contract as a spender on the old token-contract, and
+//! the issuer needs to set this contract as the minter of the new
+//! token-contract, which only allows it to mint. The issuer keeps the
+//! ownership of the new token-contract throughout the migration.
+
+#![no_std]
+#![deny(unused_extern_crates)]
+#![deny(missing_docs)]
+#![deny(rustdoc::broken_intra_doc_links)]
+#![deny(clippy::pedantic)]
+
+#[cfg(target_family = "wasm")]
+pub(crate) mod state;
+
+#[cfg(target_family = "wasm")]
+mod wasm {
+    use dusk_core::abi;
+
+    use crate::state::STATE;
+
+    /*
+     * Basic contract implementation.
+     */
+
+    #[no_mangle]
+    unsafe extern "C" fn init(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(old_token, new_token, issuer)| {
+            STATE.init(old_token, new_token, issuer);
+        })
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn old_token(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(): ()| STATE.old_token())
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn new_token(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(): ()| STATE.new_token())
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn issuer(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(): ()| STATE.issuer())
+    }
+
+    /*
+     * Migration implementation.
+     */
+
+    #[no_mangle]
+    unsafe extern "C" fn migrate(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |value| STATE.migrate(value))
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn migrated(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |holder| STATE.migrated(holder))
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn progress(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(): ()| STATE.progress())
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn close_migration(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |(): ()| STATE.close_migration())
+    }
+}
@@ -0,0 +1,240 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use dusk_core::abi::{self, ContractId, CONTRACT_ID_BYTES};
+use emt_core::migrator::{error, events, MigrationProgress};
+use emt_core::token::error::SHIELDED_NOT_SUPPORTED;
+use emt_core::{Account, Reason};
+
+const EMPTY: ContractId = ContractId::from_bytes([0u8; CONTRACT_ID_BYTES]);
+
+/// The reason code given to the new token-contract when minting migrated
+/// tokens.
+const MIGRATION_REASON_CODE: u64 = 0;
+
+/// The state of the migration-contract.
+pub struct Migrator {
+    // The contract-id of the token-contract holders migrate from.
+    old_token: ContractId,
+    // The contract-id of the token-contract holders migrate to.
+    new_token: ContractId,
+    // The issuer is the only account allowed to close the migration window.
+    issuer: Account,
+    // The value each holder has migrated so far.
+    migrated: BTreeMap<Account, u64>,
+    // The total value migrated by all holders.
+    total_migrated: u64,
+    // Whether holders can still migrate.
+    is_open: bool,
+}
+
+/// The state of the migration-contract at deployment.
+pub static mut STATE: Migrator = Migrator::new();
+
+/// Basic contract implementation.
+impl Migrator {
+    /// Create a new empty instance of the migration-contract.
+    #[must_use]
+    const fn new() -> Self {
+        Self {
+            old_token: EMPTY,
+            new_token: EMPTY,
+            issuer: Account::Contract(EMPTY),
+            migrated: BTreeMap::new(),
+            total_migrated: 0,
+            is_open: false,
+        }
+    }
+
+    /// Initialize the migration-contract with the token-contracts to migrate
+    /// between and the issuer controlling the migration window.
+    ///
+    /// The migration window is open right after initialization.
+    ///
+    /// # Panics
+    /// This function will panic if the contract is already initialized.
+    pub fn init(
+        &mut self,
+        old_token: ContractId,
+        new_token: ContractId,
+        issuer: Account,
+    ) {
+        // panic if the contract has already been initialized
+        assert!(self.old_token == EMPTY, "{}", error::ALLREADY_INITIALIZED);
+
+        self.old_token = old_token;
+        self.new_token = new_token;
+        self.issuer = issuer;
+        self.is_open = true;
+    }
+
+    /// Return the token-contract holders migrate from.
+    #[must_use]
+    pub fn old_token(&self) -> ContractId {
+        self.old_token
+    }
+
+    /// Return the token-contract holders migrate to.
+    #[must_use]
+    pub fn new_token(&self) -> ContractId {
+        self.new_token
+    }
+
+    /// Return the issuer controlling the migration window.
+    #[must_use]
+    pub fn issuer(&self) -> Account {
+        self.issuer
+    }
+}
+
+/// Migration implementation.
+impl Migrator {
+    /// Migrate `value` tokens of the caller from the old to the new
+    /// token-contract.
+    ///
+    /// The value is moved to the account of this contract on the old
+    /// token-contract, where it stays locked, and the same value is minted to
+    /// the caller on the new token-contract.
+    ///
+    /// # Panics
+    /// This function will panic if:
+    /// - The migration window is closed.
+    /// - The value is zero.
+    /// - The caller didn't approve this contract to spend `value` on the old
+    ///   token-contract, or doesn't own enough tokens there.
+    /// - The transfer on the old token-contract is queued for clearing instead
+    ///   of being executed.
+    /// - This contract is not the minter of the new token-contract.
+    pub fn migrate(&mut self, value: u64) {
+        assert!(self.is_open, "{}", error::MIGRATION_CLOSED);
+        assert!(value > 0, "{}", error::ZERO_MIGRATION);
+
+        let holder = sender_account();
+        let lock_account = Account::Contract(abi::self_id());
+
+        // lock the value in the old token-contract
+        let locked = self.old_balance(lock_account);
+        if let Err(err) = abi::call::<_, ()>(
+            self.old_token,
+            "transfer_from",
+            &(holder, lock_account, value),
+        ) {
+            panic!("Failed locking the migrated tokens: {err}");
+        }
+
+        // a transfer queued for clearing returns without moving the tokens,
+        // and could still be cancelled by the holder
+        assert!(
+            self.old_balance(lock_account) == locked + value,
+            "{}",
+            error::LOCK_NOT_SETTLED
+        );
+
+        // mint the same value in the new token-contract
+        let reason =
+            Reason::new(MIGRATION_REASON_CODE, self.old_token.to_bytes());
+        if let Err(err) =
+            abi::call::<_, ()>(self.new_token, "mint", &(holder, value, reason))
+        {
+            panic!("Failed minting the migrated tokens: {err}");
+        }
+
+        // this can never overflow as the migrated value is bounded by the
+        // supply of the old token-contract
+        *self.migrated.entry(holder).or_insert(0) += value;
+        self.total_migrated += value;
+
+        abi::emit(events::Migrated::TOPIC, events::Migrated { holder, value });
+    }
+
+    /// Return the balance of an account on the old token-contract.
+    fn old_balance(&self, account: Account) -> u64 {
+        abi::call::<_, u64>(self.old_token, "balance_of", &account)
+            .unwrap_or_else(|err| {
+                panic!("Failed querying the old balance: {err}")
+            })
+    }
+
+    /// Return the value the given holder has migrated so far.
+    #[must_use]
+    pub fn migrated(&self, holder: Account) -> u64 {
+        self.migrated.get(&holder).copied().unwrap_or(0)
+    }
+
+    /// Return the progress of the migration.
+    ///
+    /// # Panics
+    /// This function will panic if the supply of the old token-contract can't
+    /// be queried.
+    #[must_use]
+    pub fn progress(&self) -> MigrationProgress {
+        let old_supply =
+            abi::call::<_, u64>(self.old_token, "total_supply", &())
+                .unwrap_or_else(|err| {
+                    panic!("Failed querying the old supply: {err}")
+                });
+
+        MigrationProgress {
+            migrated: self.total_migrated,
+            holders: self.migrated.len() as u64,
+            old_supply,
+            open: self.is_open,
+        }
+    }
+
+    /// Close the migration window, giving up the minter role on the new
+    /// token-contract.
+    ///
+    /// # Panics
+    /// This function will panic if:
+    /// - The caller is not the issuer.
+    /// - The migration window is already closed.
+    pub fn close_migration(&mut self) {
+        assert!(
+            sender_account() == self.issuer,
+            "{}",
+            error::UNAUTHORIZED_ISSUER
+        );
+        assert!(self.is_open, "{}", error::MIGRATION_CLOSED);
+
+        self.is_open = false;
+
+        if let Err(err) =
+            abi::call::<_, ()>(self.new_token, "renounce_minter", &())
+        {
+            panic!("Failed renouncing the minter role: {err}");
+        }
+
+        abi::emit(
+            events::MigrationClosed::TOPIC,
+            events::MigrationClosed {
+                migrated: self.total_migrated,
+                holders: self.migrated.len() as u64,
+            },
+        );
+    }
+}
+
+/// Determines and returns the sender of the current call.
+///
+/// If the call is a direct transaction, return the public sender. If it is an
+/// inter-contract call, return the calling contract.
+///
+/// # Panics
+///
+/// - If no public sender is available (shielded transactions are not supported)
+/// - If no caller can be determined (impossible case)
+fn sender_account() -> Account {
+    if abi::callstack().len() == 1 {
+        Account::External(abi::public_sender().expect(SHIELDED_NOT_SUPPORTED))
+    } else {
+        Account::Contract(abi::caller().expect("ICC expects a caller"))
+    }
+}
@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::LazyLock;
+
+use dusk_core::abi::{ContractError, ContractId, StandardBufSerializer};
+use dusk_core::dusk;
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_vm::{CallReceipt, ContractData};
+
+use bytecheck::CheckBytes;
+
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{Archive, Deserialize, Infallible, Serialize};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use emt_core::migrator::MigrationProgress;
+use emt_core::{Account, AccountInfo};
+
+use emt_tests::network::NetworkSession;
+
+const TOKEN_BYTECODE: &[u8] = include_bytes!(
+    "../../target/wasm64-unknown-unknown/release/emt_token.wasm"
+);
+const MIGRATOR_BYTECODE: &[u8] = include_bytes!(
+    "../../target/wasm64-unknown-unknown/release/emt_migrator.wasm"
+);
+
+const DEPLOYER: [u8; 64] = [0u8; 64];
+
+pub const OLD_TOKEN_ID: ContractId = ContractId::from_bytes([1; 32]);
+pub const NEW_TOKEN_ID: ContractId = ContractId::from_bytes([2; 32]);
+pub const MIGRATOR_ID: ContractId = ContractId::from_bytes([3; 32]);
+
+pub const MOONLIGHT_BALANCE: u64 = dusk(1_000.0);
+pub const INITIAL_BALANCE: u64 = 1000;
+pub const INITIAL_ISSUER_BALANCE: u64 = 1000;
+pub const INITIAL_SUPPLY: u64 = INITIAL_BALANCE + INITIAL_ISSUER_BALANCE;
+
+pub struct TestSession {
+    session: NetworkSession,
+}
+
+impl TestSession {
+    /// Test session secret key of the issuer, which is the ownership of both
+    /// token-contracts.
+    pub const SK_0: LazyLock<AccountSecretKey> = LazyLock::new(|| {
+        let mut rng = StdRng::seed_from_u64(0x5EAF00D);
+        AccountSecretKey::random(&mut rng)
+    });
+
+    pub const PK_0: LazyLock<AccountPublicKey> =
+        LazyLock::new(|| AccountPublicKey::from(&*Self::SK_0));
+
+    /// Test session secret key of a holder on the old token-contract.
+    pub const SK_1: LazyLock<AccountSecretKey> = LazyLock::new(|| {
+        let mut rng = StdRng::seed_from_u64(0xF0CACC1A);
+        AccountSecretKey::random(&mut rng)
+    });
+
+    pub const PK_1: LazyLock<AccountPublicKey> =
+        LazyLock::new(|| AccountPublicKey::from(&*Self::SK_1));
+}
+
+impl TestSession {
+    pub fn new() -> Self {
+        // deploy a session with transfer & stake contract deployed
+        // pass a list of accounts to fund
+        let mut network_session = NetworkSession::instantiate(vec![
+            (&*Self::PK_0, MOONLIGHT_BALANCE),
+            (&*Self::PK_1, MOONLIGHT_BALANCE),
+        ]);
+
+        // deploy the old token-contract with the issuer as ownership
+        network_session
+            .deploy(
+                TOKEN_BYTECODE,
+                ContractData::builder()
+                    .owner(DEPLOYER)
+                    .init_arg(&(
+                        vec![
+                            (
+                                Account::from(*Self::PK_0),
+                                INITIAL_ISSUER_BALANCE,
+                            ),
+                            (Account::from(*Self::PK_1), INITIAL_BALANCE),
+                        ],
+                        Account::from(*Self::PK_0),
+                    ))
+                    .contract_id(OLD_TOKEN_ID),
+            )
+            .expect("Deploying the old token-contract should succeed");
+
+        // deploy the new token-contract with the issuer as ownership
+        network_session
+            .deploy(
+                TOKEN_BYTECODE,
+                ContractData::builder()
+                    .owner(DEPLOYER)
+                    .init_arg(&(
+                        Vec::<(Account, u64)>::new(),
+                        Account::from(*Self::PK_0),
+                    ))
+                    .contract_id(NEW_TOKEN_ID),
+            )
+            .expect("Deploying the new token-contract should succeed");
+
+        // deploy the migration-contract
+        network_session
+            .deploy(
+                MIGRATOR_BYTECODE,
+                ContractData::builder()
+                    .owner(DEPLOYER)
+                    .init_arg(&(
+                        OLD_TOKEN_ID,
+                        NEW_TOKEN_ID,
+                        Account::from(*Self::PK_0),
+                    ))
+                    .contract_id(MIGRATOR_ID),
+            )
+            .expect("Deploying the migration-contract should succeed");
+
+        let mut session = Self {
+            session: network_session,
+        };
+
+        // the migration-contract may only mint on the new token-contract
+        session
+            .call::<_, ()>(
+                NEW_TOKEN_ID,
+                &*Self::SK_0,
+                "set_minter",
+                &Some(Account::from(MIGRATOR_ID)),
+            )
+            .expect("Setting the minter should succeed");
+
+        session
+    }
+
+    /// Execute a state-transition of the given contract, paying gas with
+    /// `tx_sk`.
+    pub fn call<A, R>(
+        &mut self,
+        contract_id: ContractId,
+        tx_sk: &AccountSecretKey,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> Result<CallReceipt<R>, ContractError>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .icc_transaction(tx_sk, contract_id, fn_name, fn_arg)
+    }
+
+    /// Query the given contract directly without paying gas.
+    pub fn query<A, R>(
+        &mut self,
+        contract_id: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> R
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .direct_call(contract_id, fn_name, fn_arg)
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn balance_of(
+        &mut self,
+        token: ContractId,
+        account: impl Into<Account>,
+    ) -> u64 {
+        self.query::<_, AccountInfo>(token, "account", &account.into())
+            .balance
+    }
+
+    pub fn migrated(&mut self, holder: impl Into<Account>) -> u64 {
+        self.query(MIGRATOR_ID, "migrated", &holder.into())
+    }
+
+    pub fn progress(&mut self) -> MigrationProgress {
+        self.query(MIGRATOR_ID, "progress", &())
+    }
+}
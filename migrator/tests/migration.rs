@@ -0,0 +1,286 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::ContractError;
+
+use emt_core::migrator::{error, events, MigrationProgress};
+use emt_core::Account;
+
+pub mod instantiate;
+use instantiate::{
+    TestSession, INITIAL_BALANCE, INITIAL_SUPPLY, MIGRATOR_ID, NEW_TOKEN_ID,
+    OLD_TOKEN_ID,
+};
+
+const MIGRATED: u64 = 600;
+
+#[test]
+fn deploy() {
+    let mut session = TestSession::new();
+
+    assert_eq!(
+        session.progress(),
+        MigrationProgress {
+            migrated: 0,
+            holders: 0,
+            old_supply: INITIAL_SUPPLY,
+            open: true,
+        }
+    );
+    assert_eq!(
+        session.query::<_, Account>(NEW_TOKEN_ID, "ownership", &()),
+        Account::from(*TestSession::PK_0)
+    );
+    assert_eq!(
+        session.query::<_, Option<Account>>(NEW_TOKEN_ID, "minter", &()),
+        Some(Account::from(MIGRATOR_ID))
+    );
+
+    // the issuer keeps control over the new token-contract during the
+    // migration
+    session
+        .call::<_, ()>(NEW_TOKEN_ID, &*TestSession::SK_0, "pause", &())
+        .expect("Call should pass");
+    session
+        .call::<_, ()>(NEW_TOKEN_ID, &*TestSession::SK_0, "unpause", &())
+        .expect("Call should pass");
+}
+
+/// Test migrating without approving the migration-contract
+/// Test migrating tokens from the old to the new token-contract
+/// Test the migration progress
+#[test]
+fn migrate() {
+    let mut session = TestSession::new();
+    let holder = Account::from(*TestSession::PK_1);
+
+    // the migration-contract needs to be approved on the old token-contract
+    let receipt = session.call::<_, ()>(
+        MIGRATOR_ID,
+        &*TestSession::SK_1,
+        "migrate",
+        &MIGRATED,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert!(panic_msg.starts_with("Failed locking the migrated tokens"));
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call::<_, ()>(
+            OLD_TOKEN_ID,
+            &*TestSession::SK_1,
+            "approve",
+            &(Account::from(MIGRATOR_ID), INITIAL_BALANCE),
+        )
+        .expect("Call should pass");
+
+    let receipt = session
+        .call::<_, ()>(MIGRATOR_ID, &*TestSession::SK_1, "migrate", &MIGRATED)
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::Migrated::TOPIC
+            && rkyv::from_bytes::<events::Migrated>(&event.data).unwrap()
+                == events::Migrated {
+                    holder,
+                    value: MIGRATED,
+                }
+    }));
+
+    // the migrated tokens are locked in the old and minted in the new
+    // token-contract
+    assert_eq!(
+        session.balance_of(OLD_TOKEN_ID, holder),
+        INITIAL_BALANCE - MIGRATED
+    );
+    assert_eq!(session.balance_of(OLD_TOKEN_ID, MIGRATOR_ID), MIGRATED);
+    assert_eq!(session.balance_of(NEW_TOKEN_ID, holder), MIGRATED);
+    assert_eq!(
+        session.query::<_, u64>(NEW_TOKEN_ID, "total_supply", &()),
+        MIGRATED
+    );
+
+    // migrating the rest adds to the same holder
+    session
+        .call::<_, ()>(
+            MIGRATOR_ID,
+            &*TestSession::SK_1,
+            "migrate",
+            &(INITIAL_BALANCE - MIGRATED),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.balance_of(OLD_TOKEN_ID, holder), 0);
+    assert_eq!(session.balance_of(NEW_TOKEN_ID, holder), INITIAL_BALANCE);
+    assert_eq!(session.migrated(holder), INITIAL_BALANCE);
+    assert_eq!(
+        session.progress(),
+        MigrationProgress {
+            migrated: INITIAL_BALANCE,
+            holders: 1,
+            old_supply: INITIAL_SUPPLY,
+            open: true,
+        }
+    );
+
+    // a zero value can't be migrated
+    let receipt = session.call::<_, ()>(
+        MIGRATOR_ID,
+        &*TestSession::SK_1,
+        "migrate",
+        &0u64,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::ZERO_MIGRATION);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+/// Test migrating a value the old token-contract queues for clearing
+#[test]
+fn migrate_above_clearing_threshold() {
+    let mut session = TestSession::new();
+    let holder = Account::from(*TestSession::PK_1);
+
+    session
+        .call::<_, ()>(
+            OLD_TOKEN_ID,
+            &*TestSession::SK_0,
+            "set_clearing",
+            &(Some(MIGRATED - 1), None::<Account>),
+        )
+        .expect("Call should pass");
+    session
+        .call::<_, ()>(
+            OLD_TOKEN_ID,
+            &*TestSession::SK_1,
+            "approve",
+            &(Account::from(MIGRATOR_ID), INITIAL_BALANCE),
+        )
+        .expect("Call should pass");
+
+    let receipt = session.call::<_, ()>(
+        MIGRATOR_ID,
+        &*TestSession::SK_1,
+        "migrate",
+        &MIGRATED,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::LOCK_NOT_SETTLED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // nothing is locked or minted
+    assert_eq!(session.balance_of(OLD_TOKEN_ID, holder), INITIAL_BALANCE);
+    assert_eq!(session.balance_of(OLD_TOKEN_ID, MIGRATOR_ID), 0);
+    assert_eq!(session.balance_of(NEW_TOKEN_ID, holder), 0);
+    assert_eq!(session.migrated(holder), 0);
+
+    // values below the threshold still migrate
+    session
+        .call::<_, ()>(
+            MIGRATOR_ID,
+            &*TestSession::SK_1,
+            "migrate",
+            &(MIGRATED - 1),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.balance_of(NEW_TOKEN_ID, holder), MIGRATED - 1);
+}
+
+/// Test closing the migration window by anyone but the issuer
+/// Test closing the migration window
+/// Test migrating after the migration window is closed
+#[test]
+fn close_migration() {
+    let mut session = TestSession::new();
+    let issuer = Account::from(*TestSession::PK_0);
+
+    session
+        .call::<_, ()>(
+            OLD_TOKEN_ID,
+            &*TestSession::SK_1,
+            "approve",
+            &(Account::from(MIGRATOR_ID), INITIAL_BALANCE),
+        )
+        .expect("Call should pass");
+    session
+        .call::<_, ()>(MIGRATOR_ID, &*TestSession::SK_1, "migrate", &MIGRATED)
+        .expect("Call should pass");
+
+    let receipt = session.call::<_, ()>(
+        MIGRATOR_ID,
+        &*TestSession::SK_1,
+        "close_migration",
+        &(),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ISSUER);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call::<_, ()>(MIGRATOR_ID, &*TestSession::SK_0, "close_migration", &())
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::MigrationClosed::TOPIC
+            && rkyv::from_bytes::<events::MigrationClosed>(&event.data).unwrap()
+                == events::MigrationClosed {
+                    migrated: MIGRATED,
+                    holders: 1,
+                }
+    }));
+
+    // the migration-contract can't mint anymore
+    assert_eq!(
+        session.query::<_, Option<Account>>(NEW_TOKEN_ID, "minter", &()),
+        None
+    );
+    assert_eq!(
+        session.query::<_, Account>(NEW_TOKEN_ID, "ownership", &()),
+        issuer
+    );
+    assert!(!session.progress().open);
+
+    // holders can't migrate anymore
+    let receipt = session.call::<_, ()>(
+        MIGRATOR_ID,
+        &*TestSession::SK_1,
+        "migrate",
+        &(INITIAL_BALANCE - MIGRATED),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::MIGRATION_CLOSED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the window can only be closed once
+    let receipt = session.call::<_, ()>(
+        MIGRATOR_ID,
+        &*TestSession::SK_0,
+        "close_migration",
+        &(),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::MIGRATION_CLOSED);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Test contract that can be registered as the compliance contract of the
+//! token-contract.
+//!
+//! It vetoes any operation involving a restricted account and keeps track of
+//! the operations the token-contract notifies it of.
+//!
+//! Anyone can call the `restrict` function to restrict an account.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractId};
+
+use emt_core::*;
+
+struct ComplianceState {
+    token_contract: ContractId,
+    restricted: BTreeSet<Account>,
+    /// The operations executed by the token-contract
+    transfers: Vec<(Account, Account, u64)>,
+}
+
+static mut STATE: ComplianceState = ComplianceState {
+    token_contract: ContractId::from_bytes([0u8; 32]),
+    restricted: BTreeSet::new(),
+    transfers: Vec::new(),
+};
+
+impl ComplianceState {
+    fn init(&mut self, token_contract: ContractId) {
+        self.token_contract = token_contract;
+    }
+
+    fn restrict(&mut self, account: Account) {
+        self.restricted.insert(account);
+    }
+
+    fn can_transfer(&self, sender: Account, receiver: Account) -> bool {
+        !self.restricted.contains(&sender)
+            && !self.restricted.contains(&receiver)
+    }
+
+    /// Records an operation executed by the token-contract.
+    fn transferred(&mut self, sender: Account, receiver: Account, value: u64) {
+        assert_eq!(
+            abi::caller().expect("Expected a contract as caller"),
+            self.token_contract,
+            "Only the token-contract can notify transfers"
+        );
+
+        self.transfers.push((sender, receiver, value));
+    }
+
+    fn transfers(&self) -> Vec<(Account, Account, u64)> {
+        self.transfers.clone()
+    }
+}
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_contract| STATE.init(token_contract))
+}
+
+#[no_mangle]
+unsafe fn restrict(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |account| STATE.restrict(account))
+}
+
+#[no_mangle]
+unsafe fn can_transfer(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(sender, receiver, _value): (_, _, u64)| {
+        STATE.can_transfer(sender, receiver)
+    })
+}
+
+#[no_mangle]
+unsafe fn transferred(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(sender, receiver, value)| {
+        STATE.transferred(sender, receiver, value)
+    })
+}
+
+#[no_mangle]
+unsafe fn transfers(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(): ()| STATE.transfers())
+}
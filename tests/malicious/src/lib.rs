@@ -0,0 +1,85 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Test contract that tries to re-enter the token-contract when it receives
+//! tokens through `transfer_and_call`.
+//!
+//! Anyone can set the call the contract makes to the token-contract when its
+//! `token_received` function is called. The error returned by that call, if
+//! any, is recorded instead of propagated, so that the outer
+//! `transfer_and_call` succeeds and the outcome of the re-entrant call can be
+//! queried.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractError, ContractId};
+
+struct State {
+    token_contract: ContractId,
+    /// The function and arguments of the call made to the token-contract
+    /// when tokens are received
+    attack: Option<(String, Vec<u8>)>,
+    /// The error returned by the last attack, if it failed
+    attack_error: Option<ContractError>,
+}
+
+impl State {
+    fn init(&mut self, token_contract: ContractId) {
+        self.token_contract = token_contract;
+    }
+
+    /// Can be called by anyone to set the call made to the token-contract
+    /// when tokens are received.
+    fn set_attack(&mut self, fn_name: String, fn_args: Vec<u8>) {
+        self.attack = Some((fn_name, fn_args));
+    }
+
+    /// Handles incoming token transfers by calling the token-contract again,
+    /// while its `transfer_and_call` is still in flight.
+    fn token_received(&mut self) {
+        if let Some((fn_name, fn_args)) = &self.attack {
+            self.attack_error =
+                abi::call_raw(self.token_contract, fn_name, fn_args).err();
+        }
+    }
+
+    fn attack_error(&self) -> Option<ContractError> {
+        self.attack_error.clone()
+    }
+}
+
+static mut STATE: State = State {
+    token_contract: ContractId::from_bytes([0u8; 32]),
+    attack: None,
+    attack_error: None,
+};
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_contract| STATE.init(token_contract))
+}
+
+#[no_mangle]
+unsafe fn set_attack(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(fn_name, fn_args)| {
+        STATE.set_attack(fn_name, fn_args)
+    })
+}
+
+#[no_mangle]
+unsafe fn token_received(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(): ()| STATE.token_received())
+}
+
+#[no_mangle]
+unsafe fn attack_error(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(): ()| STATE.attack_error())
+}
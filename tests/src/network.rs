@@ -13,18 +13,29 @@ use dusk_core::signatures::bls::{
 use dusk_core::stake::STAKE_CONTRACT;
 use dusk_core::transfer::data::ContractCall;
 use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::phoenix::{
+    Note, NoteOpening, PublicKey as PhoenixPublicKey,
+    SecretKey as PhoenixSecretKey,
+};
 use dusk_core::transfer::{Transaction, TRANSFER_CONTRACT};
-use dusk_core::LUX;
+use dusk_core::{BlsScalar, JubJubScalar, LUX};
 use dusk_vm::{execute, ExecutionConfig};
 use dusk_vm::{CallReceipt, ContractData, Error as VMError, Session, VM};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 use rkyv::validation::validators::DefaultValidator;
 use rkyv::{Archive, Deserialize, Infallible, Serialize};
 
-use crate::utils::{account, chain_id, rkyv_deserialize, rkyv_serialize};
+use crate::utils::{
+    account, chain_id, rkyv_deserialize, rkyv_serialize, unverified_proofs_vm,
+    NoProver,
+};
 
 const ZERO_ADDRESS: ContractId = ContractId::from_bytes([0; CONTRACT_ID_BYTES]);
 const GAS_LIMIT: u64 = 0x10000000;
-const CHAIN_ID: u8 = 0x1;
+/// The chain id of the sessions.
+pub const CHAIN_ID: u8 = 0x1;
+const BLOCK_HEIGHT: u64 = 1;
 const NO_CONFIG: ExecutionConfig = ExecutionConfig::DEFAULT;
 
 type Result<T, Error = VMError> = core::result::Result<T, Error>;
@@ -34,8 +45,10 @@ type Result<T, Error = VMError> = core::result::Result<T, Error>;
 /// Calls go as tx through the genesis transfer contract,
 /// before reaching the non-genesis contract.
 pub struct NetworkSession {
+    vm: VM,
     session: Session,
     config: ExecutionConfig,
+    block_height: u64,
 }
 
 impl NetworkSession {
@@ -87,6 +100,32 @@ impl NetworkSession {
         fn_name: &str,
         fn_arg: &A,
     ) -> Result<CallReceipt<R>, ContractError>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.icc_transaction_with_gas_limit(
+            moonlight_sk,
+            contract,
+            fn_name,
+            fn_arg,
+            GAS_LIMIT,
+        )
+    }
+
+    /// Calls the contract trough the transfer-contract like
+    /// [`icc_transaction`](Self::icc_transaction), with the given gas limit.
+    pub fn icc_transaction_with_gas_limit<A, R>(
+        &mut self,
+        moonlight_sk: &AccountSecretKey,
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+        gas_limit: u64,
+    ) -> Result<CallReceipt<R>, ContractError>
     where
         A: for<'b> Serialize<StandardBufSerializer<'b>>,
         A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
@@ -111,7 +150,7 @@ impl NetworkSession {
             None,
             0,
             0,
-            GAS_LIMIT,
+            gas_limit,
             LUX,
             nonce + 1,
             CHAIN_ID,
@@ -119,7 +158,130 @@ impl NetworkSession {
         )
         .expect("Creating moonlight transaction should succeed");
 
-        let receipt = execute(&mut self.session, &transaction, &self.config)
+        self.execute_transaction(&transaction)
+    }
+
+    /// Calls the contract trough the transfer-contract with a shielded
+    /// (Phoenix) transaction. The gas is paid using the given `note` owned by
+    /// the `phoenix_sk`, which is spent entirely and refunded to the owner.
+    ///
+    /// Note: The transaction is not proven, so it is only accepted by
+    /// sessions created with [`NetworkSession::instantiate_unverified`].
+    pub fn phoenix_icc_transaction<A, R>(
+        &mut self,
+        phoenix_sk: &PhoenixSecretKey,
+        note: Note,
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> Result<CallReceipt<R>, ContractError>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        let contract_call = ContractCall {
+            contract,
+            fn_name: String::from(fn_name),
+            fn_args: rkyv_serialize(fn_arg),
+        };
+
+        let opening = self
+            .session
+            .call::<_, Option<NoteOpening>>(
+                TRANSFER_CONTRACT,
+                "opening",
+                note.pos(),
+                GAS_LIMIT,
+            )
+            .expect("Getting the opening should succeed")
+            .data
+            .expect("The note should exist in the tree");
+        let root = self
+            .session
+            .call::<_, BlsScalar>(TRANSFER_CONTRACT, "root", &(), GAS_LIMIT)
+            .expect("Getting the root should succeed")
+            .data;
+
+        let phoenix_pk = PhoenixPublicKey::from(phoenix_sk);
+        let mut rng = StdRng::seed_from_u64(*note.pos());
+
+        let transaction = Transaction::phoenix(
+            &mut rng,
+            phoenix_sk,
+            &phoenix_pk,
+            &phoenix_pk,
+            vec![(note, opening)],
+            root,
+            0,
+            false,
+            0,
+            GAS_LIMIT,
+            LUX,
+            CHAIN_ID,
+            Some(contract_call),
+            &NoProver,
+        )
+        .expect("Creating phoenix transaction should succeed");
+
+        self.execute_transaction(&transaction)
+    }
+
+    /// Creates a new transparent note of the given value, owned by the
+    /// `phoenix_pk`, that can be used to pay for shielded (Phoenix)
+    /// transactions.
+    pub fn fund_phoenix(
+        &mut self,
+        phoenix_pk: &PhoenixPublicKey,
+        value: u64,
+    ) -> Note {
+        let num_notes = self
+            .session
+            .call::<_, u64>(TRANSFER_CONTRACT, "num_notes", &(), GAS_LIMIT)
+            .expect("Getting the number of notes should succeed")
+            .data;
+        let mut rng = StdRng::seed_from_u64(num_notes);
+        let sender_blinder = [
+            JubJubScalar::from(rng.next_u64()),
+            JubJubScalar::from(rng.next_u64()),
+        ];
+        let note = Note::transparent(
+            &mut rng,
+            phoenix_pk,
+            phoenix_pk,
+            value,
+            sender_blinder,
+        );
+
+        let note = self
+            .session
+            .call::<_, Note>(
+                TRANSFER_CONTRACT,
+                "push_note",
+                &(self.block_height, note),
+                GAS_LIMIT,
+            )
+            .expect("Pushing the note should succeed")
+            .data;
+        self.session
+            .call::<_, ()>(TRANSFER_CONTRACT, "update_root", &(), GAS_LIMIT)
+            .expect("Updating the root should succeed");
+
+        note
+    }
+
+    fn execute_transaction<R>(
+        &mut self,
+        transaction: &Transaction,
+    ) -> Result<CallReceipt<R>, ContractError>
+    where
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        let receipt = execute(&mut self.session, transaction, &self.config)
             .unwrap_or_else(|e| {
                 panic!("Executing the transaction should succeed: {:?}", e)
             });
@@ -138,14 +300,45 @@ impl NetworkSession {
 }
 
 impl NetworkSession {
+    /// Commit the current state and continue in a new session the given
+    /// number of blocks later.
+    pub fn advance_block_height(&mut self, blocks: u64) {
+        let session = core::mem::replace(
+            &mut self.session,
+            self.vm.genesis_session(CHAIN_ID),
+        );
+        let base = session.commit().expect("Committing should succeed");
+
+        self.block_height += blocks;
+        self.session = self
+            .vm
+            .session(base, CHAIN_ID, self.block_height)
+            .expect("Instantiating new session should succeed");
+    }
+
     /// Instantiate the virtual machine with both the transfer and stake
     /// contract deployed. The given public accounts own the specified
     /// amount of DUSK token in order to pay for transactions like deploying
     /// or executing contracts.
     pub fn instantiate(pks_to_fund: Vec<(&AccountPublicKey, u64)>) -> Self {
         let vm = VM::ephemeral().expect("Creating VM should succeed");
+        Self::instantiate_with_vm(vm, pks_to_fund)
+    }
+
+    /// Instantiate the virtual machine like [`NetworkSession::instantiate`],
+    /// but without verifying the proofs of shielded (Phoenix) transactions,
+    /// so that they can be executed by the tests.
+    pub fn instantiate_unverified(
+        pks_to_fund: Vec<(&AccountPublicKey, u64)>,
+    ) -> Self {
+        Self::instantiate_with_vm(unverified_proofs_vm(), pks_to_fund)
+    }
 
-        let mut session = VM::genesis_session(&vm, 1);
+    fn instantiate_with_vm(
+        vm: VM,
+        pks_to_fund: Vec<(&AccountPublicKey, u64)>,
+    ) -> Self {
+        let mut session = vm.genesis_session(CHAIN_ID);
 
         // deploy transfer contract
         let transfer_contract =
@@ -193,7 +386,7 @@ impl NetworkSession {
 
         // start a new session from that base-commit
         let mut session = vm
-            .session(base, CHAIN_ID, 1)
+            .session(base, CHAIN_ID, BLOCK_HEIGHT)
             .expect("Instantiating new session should succeed");
 
         // assert that the accounts are instantiated with their expected value
@@ -217,6 +410,11 @@ impl NetworkSession {
         let mut config = NO_CONFIG;
         config.with_public_sender = true;
 
-        Self { session, config }
+        Self {
+            vm,
+            session,
+            config,
+            block_height: BLOCK_HEIGHT,
+        }
     }
 }
@@ -5,13 +5,20 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use bytecheck::CheckBytes;
-use dusk_core::abi::StandardBufSerializer;
+use dusk_core::abi::{Query, StandardBufSerializer};
 use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
 use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::phoenix::Prove;
 use dusk_core::transfer::TRANSFER_CONTRACT;
-use dusk_vm::{Error as VMError, Session};
+use dusk_core::Error as CoreError;
+use dusk_vm::host_queries::{
+    hash, poseidon_hash, verify_bls, verify_bls_multisig, verify_groth16_bn254,
+    verify_schnorr,
+};
+use dusk_vm::{Error as VMError, Session, VM};
+use piecrust::VM as PiecrustVM;
 use rkyv::ser::serializers::{
-    BufferScratch, BufferSerializer, CompositeSerializer,
+    AllocSerializer, BufferScratch, BufferSerializer, CompositeSerializer,
 };
 use rkyv::ser::Serializer;
 use rkyv::validation::validators::DefaultValidator;
@@ -71,3 +78,98 @@ pub fn account(
         .call(TRANSFER_CONTRACT, "account", pk, GAS_LIMIT)
         .map(|r| r.data)
 }
+
+/// Instantiate an ephemeral virtual machine with the host-queries of the Dusk
+/// VM, except for the verification of PLONK proofs which always succeeds.
+///
+/// The prover keys matching the verifier data of the genesis
+/// transfer-contract are not available to the tests, which is why the proofs
+/// of shielded (Phoenix) transactions can't be verified. This VM must only be
+/// used by the tests executing shielded transactions.
+pub fn unverified_proofs_vm() -> VM {
+    let mut vm = PiecrustVM::ephemeral().expect("Creating VM should succeed");
+
+    vm.register_host_query(Query::HASH, |buf: &mut [u8], len: u32| {
+        wrap_host_query(buf, len, hash)
+    });
+    vm.register_host_query(Query::POSEIDON_HASH, |buf: &mut [u8], len: u32| {
+        wrap_host_query(buf, len, poseidon_hash)
+    });
+    vm.register_host_query(Query::VERIFY_PLONK, |buf: &mut [u8], _len: u32| {
+        write_host_query_result(buf, &true)
+    });
+    vm.register_host_query(
+        Query::VERIFY_GROTH16_BN254,
+        |buf: &mut [u8], len: u32| {
+            wrap_host_query(buf, len, |(pvk, proof, inputs)| {
+                verify_groth16_bn254(pvk, proof, inputs)
+            })
+        },
+    );
+    vm.register_host_query(
+        Query::VERIFY_SCHNORR,
+        |buf: &mut [u8], len: u32| {
+            wrap_host_query(buf, len, |(msg, pk, sig)| {
+                verify_schnorr(msg, pk, sig)
+            })
+        },
+    );
+    vm.register_host_query(Query::VERIFY_BLS, |buf: &mut [u8], len: u32| {
+        wrap_host_query(buf, len, |(msg, pk, sig)| verify_bls(msg, pk, sig))
+    });
+    vm.register_host_query(
+        Query::VERIFY_BLS_MULTISIG,
+        |buf: &mut [u8], len: u32| {
+            wrap_host_query(buf, len, |(msg, keys, sig)| {
+                verify_bls_multisig(msg, keys, sig)
+            })
+        },
+    );
+
+    vm.into()
+}
+
+/// Prover for shielded (Phoenix) transactions executed on the
+/// [`unverified_proofs_vm`],
+/// which doesn't verify the proofs.
+pub struct NoProver;
+
+impl Prove for NoProver {
+    fn prove(
+        &self,
+        _tx_circuit_vec_bytes: &[u8],
+    ) -> Result<Vec<u8>, CoreError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Deserialize the argument of a host-query, execute the query and write the
+/// result back into the argument buffer.
+/// This mimics the host-queries registered on the Dusk VM.
+fn wrap_host_query<A, R, F>(arg_buf: &mut [u8], arg_len: u32, closure: F) -> u32
+where
+    F: FnOnce(A) -> R,
+    A: Archive,
+    A::Archived: Deserialize<A, Infallible>,
+    R: Serialize<AllocSerializer<1024>>,
+{
+    let root =
+        unsafe { rkyv::archived_root::<A>(&arg_buf[..arg_len as usize]) };
+    let arg: A = root
+        .deserialize(&mut Infallible)
+        .expect("Failed to deserialize the host-query argument");
+
+    write_host_query_result(arg_buf, &closure(arg))
+}
+
+/// Write the result of a host-query into the argument buffer.
+fn write_host_query_result<R>(arg_buf: &mut [u8], result: &R) -> u32
+where
+    R: Serialize<AllocSerializer<1024>>,
+{
+    let bytes = rkyv::to_bytes::<_, 1024>(result)
+        .expect("Failed to serialize the host-query result");
+
+    arg_buf[..bytes.len()].copy_from_slice(&bytes);
+    bytes.len() as u32
+}
@@ -13,72 +13,422 @@
 
 extern crate alloc;
 
-use alloc::collections::BTreeMap;
+use alloc::collections::{btree_map, BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::ops::Bound;
+use core::ptr::{addr_of, addr_of_mut};
 
-use dusk_core::abi;
+use dusk_core::abi::{self, ContractId, StandardBufSerializer};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
 use dusk_core::transfer::data::ContractCall;
-use emt_core::token::error;
+use dusk_merkle::{Aggregate, Tree};
 use emt_core::token::events;
-use emt_core::{Account, AccountInfo, ZERO_ADDRESS};
+use emt_core::token::merkle::{self, BalanceProof};
+use emt_core::token::{compliance, error};
+use emt_core::{
+    Account, AccountAttributes, AccountInfo, CurrencyId, CurrencyMetadata,
+    Document, ExportedAccount, Hold, InvariantReport, InvariantViolation,
+    Jurisdiction, PendingTransfer, Reason, RedemptionRequest,
+    ReserveAttestation, SignedCall, SupplyBreakdown, TransferStatus,
+    DEFAULT_CURRENCY, RESERVED_ADDRESSES, ZERO_ADDRESS,
+};
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{
+    AlignedVec, Archive, CheckBytes, Deserialize, Infallible, Serialize,
+};
+
+/// The key under which an account is stored in the state.
+///
+/// Accounts are stored under the hash of their raw encoding, so that looking
+/// them up compares fixed 32-byte keys instead of converting the public keys
+/// to their raw encoding on every comparison.
+type AccountKey = [u8; 32];
+
+/// An account together with its [`AccountKey`].
+///
+/// The key of an account is computed once, where the account enters the
+/// contract, and passed along with it to the functions accessing the state.
+#[derive(Clone, Copy)]
+struct KeyedAccount {
+    account: Account,
+    key: AccountKey,
+}
+
+impl KeyedAccount {
+    fn new(account: Account) -> Self {
+        Self {
+            account,
+            key: account_key(account),
+        }
+    }
+}
+
+/// The Merkle tree over the balances of the accounts.
+type BalancesTree = Tree<BalanceNode, { merkle::HEIGHT }, 2>;
+
+/// A node of the [`BalancesTree`], hashed on the host.
+#[derive(Clone, Copy)]
+struct BalanceNode([u8; 32]);
+
+impl Aggregate<2> for BalanceNode {
+    const EMPTY_SUBTREE: Self = Self(merkle::EMPTY_SUBTREE);
+
+    fn aggregate([left, right]: [&Self; 2]) -> Self {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(&left.0);
+        bytes.extend_from_slice(&right.0);
+        Self(abi::hash(bytes).to_bytes())
+    }
+}
 
 /// The state of the token-contract.
 struct TokenState {
-    accounts: BTreeMap<Account, AccountInfo>,
-    allowances: BTreeMap<Account, BTreeMap<Account, u64>>,
+    currency: CurrencyId,
+    metadata: Option<CurrencyMetadata>,
+    currencies: BTreeMap<CurrencyId, TokenState>,
+
+    accounts: BTreeMap<AccountKey, (Account, AccountInfo)>,
+    allowances: BTreeMap<(AccountKey, AccountKey), (Account, u64)>,
+    operators: BTreeMap<(AccountKey, AccountKey), Account>,
+    balances_tree: BalancesTree,
+    leaf_positions: BTreeMap<AccountKey, u64>,
+    free_positions: Vec<u64>,
+    modified_accounts: BTreeSet<AccountKey>,
+    sanction_reasons: BTreeMap<AccountKey, (Account, Reason)>,
     supply: u64,
+    /// The total amount ever minted. Wider than the supply, so that it
+    /// can't overflow however many tokens are minted and burned over time.
+    minted: u128,
+    /// The total amount ever burned.
+    burned: u128,
+    minter: Option<Account>,
+
+    redemptions: BTreeMap<u64, RedemptionRequest>,
+    next_redemption_id: u64,
+
+    holds: BTreeMap<u64, Hold>,
+    next_hold_id: u64,
+
+    pending_transfers: BTreeMap<u64, PendingTransfer>,
+    next_pending_transfer_id: u64,
+    clearing_threshold: Option<u64>,
+    clearing_agent: Option<Account>,
+
+    compliance: Option<ContractId>,
+
+    attributes: BTreeMap<AccountKey, AccountAttributes>,
+    attribute_manager: Option<Account>,
+    restricted_jurisdictions: BTreeSet<(Jurisdiction, Jurisdiction)>,
+
+    documents: BTreeMap<String, Document>,
+
+    attestor: Option<Account>,
+    attestation_validity: Option<u64>,
+    reserve_attestation: Option<ReserveAttestation>,
+
+    reserved_addresses: BTreeSet<AccountKey>,
+
+    imported_accounts: BTreeSet<AccountKey>,
+    import_finalized: bool,
+
+    signed_call_nonces: BTreeMap<AccountKey, u64>,
 
     ownership: Account,
+    guardians: BTreeMap<AccountKey, Account>,
 
     is_paused: bool,
 }
 
 impl TokenState {
+    const fn new(currency: CurrencyId) -> Self {
+        Self {
+            currency,
+            metadata: None,
+            currencies: BTreeMap::new(),
+            accounts: BTreeMap::new(),
+            allowances: BTreeMap::new(),
+            operators: BTreeMap::new(),
+            balances_tree: BalancesTree::new(),
+            leaf_positions: BTreeMap::new(),
+            free_positions: Vec::new(),
+            modified_accounts: BTreeSet::new(),
+            sanction_reasons: BTreeMap::new(),
+            supply: 0,
+            minted: 0,
+            burned: 0,
+            minter: None,
+            redemptions: BTreeMap::new(),
+            next_redemption_id: 0,
+            holds: BTreeMap::new(),
+            next_hold_id: 0,
+            pending_transfers: BTreeMap::new(),
+            next_pending_transfer_id: 0,
+            clearing_threshold: None,
+            clearing_agent: None,
+            compliance: None,
+            attributes: BTreeMap::new(),
+            attribute_manager: None,
+            restricted_jurisdictions: BTreeSet::new(),
+            documents: BTreeMap::new(),
+            attestor: None,
+            attestation_validity: None,
+            reserve_attestation: None,
+            reserved_addresses: BTreeSet::new(),
+            imported_accounts: BTreeSet::new(),
+            import_finalized: false,
+            signed_call_nonces: BTreeMap::new(),
+            ownership: ZERO_ADDRESS,
+            guardians: BTreeMap::new(),
+            is_paused: false,
+        }
+    }
+
+    /// Emit an event of the currency. Events of currencies other than the
+    /// default currency are wrapped in a [`events::CurrencyEvent`].
+    fn emit<E>(&self, topic: &'static str, event: E)
+    where
+        for<'a> E: Serialize<StandardBufSerializer<'a>>,
+    {
+        let sequence = next_event_sequence();
+        if self.currency == DEFAULT_CURRENCY {
+            abi::emit(topic, events::SequencedEvent { sequence, event });
+        } else {
+            abi::emit(
+                topic,
+                events::SequencedEvent {
+                    sequence,
+                    event: events::CurrencyEvent {
+                        currency: self.currency,
+                        event,
+                    },
+                },
+            );
+        }
+    }
+
+    /// Emit a transfer event of the currency, in the compact encoding
+    /// unless the `legacy-events` feature is enabled.
+    #[cfg(not(feature = "legacy-events"))]
+    #[allow(clippy::large_types_passed_by_value)]
+    fn emit_transfer_event(
+        &self,
+        topic: &'static str,
+        event: events::Transfer,
+    ) {
+        let bytes = emt_core::token::compact::encode_transfer(
+            next_event_sequence(),
+            self.currency,
+            &event,
+        );
+        abi::emit_raw(topic, bytes);
+    }
+
+    /// Emit a transfer event of the currency, in the same encoding as any
+    /// other event.
+    #[cfg(feature = "legacy-events")]
+    #[allow(clippy::large_types_passed_by_value)]
+    fn emit_transfer_event(
+        &self,
+        topic: &'static str,
+        event: events::Transfer,
+    ) {
+        self.emit(topic, event);
+    }
+
     fn init(&mut self, accounts: Vec<(Account, u64)>, ownership: Account) {
+        let ownership = KeyedAccount::new(ownership);
+        assert!(!self.reserved(ownership), "{}", error::RESERVED_ADDRESS);
+
         for (account, balance) in accounts {
-            let account_entry =
-                self.accounts.entry(account).or_insert(AccountInfo::EMPTY);
+            let keyed = KeyedAccount::new(account);
+            assert!(!self.reserved(keyed), "{}", error::RESERVED_ADDRESS);
+
+            let account_entry = self.account_entry(keyed);
             account_entry.balance += balance;
             self.supply += balance;
+            self.minted += u128::from(balance);
 
-            abi::emit(
+            self.emit_transfer_event(
                 events::Transfer::MINT_TOPIC,
                 events::Transfer {
                     sender: ZERO_ADDRESS,
                     spender: None,
                     receiver: account,
                     value: balance,
+                    reason: None,
                 },
             );
         }
 
         // Set the ownership
-        self.ownership = ownership;
+        self.ownership = ownership.account;
 
         // Always insert ownership
+        self.account_entry(ownership);
+
+        self.emit(
+            events::OwnershipTransferred::OWNERSHIP_TRANSFERRED,
+            events::OwnershipTransferred {
+                previous_ownership: ZERO_ADDRESS,
+                new_ownership: ownership.account,
+            },
+        );
+    }
+}
+
+/// Account storage implementation.
+impl TokenState {
+    fn account_info(&self, account: KeyedAccount) -> Option<&AccountInfo> {
+        self.accounts
+            .get(&account.key)
+            .map(|(_, account_info)| account_info)
+    }
+
+    /// Return the entry of an account for modification, marking its leaf in
+    /// the balances tree for update.
+    fn account_info_mut(
+        &mut self,
+        account: KeyedAccount,
+    ) -> Option<&mut AccountInfo> {
+        self.mark_modified(account.key);
+
         self.accounts
-            .entry(self.ownership)
-            .or_insert(AccountInfo::EMPTY);
+            .get_mut(&account.key)
+            .map(|(_, account_info)| account_info)
+    }
+
+    /// Return the entry of an account for modification, inserting an empty
+    /// entry if the account has none.
+    fn account_entry(&mut self, account: KeyedAccount) -> &mut AccountInfo {
+        self.mark_modified(account.key);
+
+        let (_, account_info) = self
+            .accounts
+            .entry(account.key)
+            .or_insert((account.account, AccountInfo::EMPTY));
+        account_info
+    }
+
+    fn allowance_mut(
+        &mut self,
+        owner: KeyedAccount,
+        spender: KeyedAccount,
+    ) -> Option<&mut u64> {
+        self.allowances
+            .get_mut(&(owner.key, spender.key))
+            .map(|(_, allowance)| allowance)
+    }
+
+    /// Return the allowance of a spender, inserting a zero allowance if the
+    /// spender has none.
+    fn allowance_entry(
+        &mut self,
+        owner: KeyedAccount,
+        spender: KeyedAccount,
+    ) -> &mut u64 {
+        let (_, allowance) = self
+            .allowances
+            .entry((owner.key, spender.key))
+            .or_insert((spender.account, 0));
+        allowance
+    }
+
+    fn has_allowances(&self, owner: KeyedAccount) -> bool {
+        entries_of(&self.allowances, owner.key).next().is_some()
+    }
+
+    fn has_operators(&self, holder: KeyedAccount) -> bool {
+        entries_of(&self.operators, holder.key).next().is_some()
+    }
+
+    /// Return the accounts in storage order, starting after the account
+    /// `after`, or from the first account if `None`.
+    fn accounts_after(
+        &self,
+        after: Option<Account>,
+    ) -> btree_map::Range<'_, AccountKey, (Account, AccountInfo)> {
+        match after {
+            Some(after) => self
+                .accounts
+                .range((Bound::Excluded(account_key(after)), Bound::Unbounded)),
+            None => self.accounts.range::<AccountKey, _>(..),
+        }
+    }
+}
+
+/// The state of the default currency, which also holds the states of the
+/// other currencies.
+static mut STATE: TokenState = TokenState::new(DEFAULT_CURRENCY);
+
+/// The signer of the signed call currently being executed, which takes the
+/// place of the sender for the duration of the call, and the length of the
+/// call stack the call is executed at.
+static mut SIGNED_SENDER: Option<(Account, usize)> = None;
+
+/// The sequence number of the last event emitted, shared by all currencies.
+static mut EVENT_SEQUENCE: u64 = 0;
+
+/// Whether the receiver of a `transfer_and_call` is being called, in any
+/// currency. See [`assert_not_entered`].
+static mut ENTERED: bool = false;
+
+/// Currency management implementation.
+impl TokenState {
+    fn currencies(&self) -> Vec<CurrencyId> {
+        let mut currencies = Vec::with_capacity(self.currencies.len() + 1);
+        currencies.push(DEFAULT_CURRENCY);
+        currencies.extend(self.currencies.keys());
+        currencies
+    }
+
+    /// Create a new currency with its own supply, balances, allowances,
+    /// ownership and pause state.
+    ///
+    /// # Note
+    /// only the ownership of the default currency can create currencies.
+    fn create_currency(
+        &mut self,
+        currency: CurrencyId,
+        metadata: CurrencyMetadata,
+        ownership: Account,
+    ) {
+        self.authorize_ownership();
+
+        assert!(
+            currency != DEFAULT_CURRENCY
+                && !self.currencies.contains_key(&currency),
+            "The currency already exists"
+        );
 
-        abi::emit(
+        let mut state = TokenState::new(currency);
+        let keyed = KeyedAccount::new(ownership);
+        assert!(!state.reserved(keyed), "{}", error::RESERVED_ADDRESS);
+
+        state.metadata = Some(metadata.clone());
+        state.ownership = ownership;
+        state.account_entry(keyed);
+
+        self.emit(
+            events::CurrencyCreated::TOPIC,
+            events::CurrencyCreated {
+                currency,
+                metadata,
+                ownership,
+            },
+        );
+        state.emit(
             events::OwnershipTransferred::OWNERSHIP_TRANSFERRED,
             events::OwnershipTransferred {
                 previous_ownership: ZERO_ADDRESS,
                 new_ownership: ownership,
             },
         );
+
+        self.currencies.insert(currency, state);
     }
 }
 
-static mut STATE: TokenState = TokenState {
-    accounts: BTreeMap::new(),
-    allowances: BTreeMap::new(),
-    supply: 0,
-    ownership: ZERO_ADDRESS,
-    is_paused: false,
-};
-
 /// Access control implementation.
 impl TokenState {
     fn ownership(&self) -> Account {
@@ -86,8 +436,7 @@ impl TokenState {
     }
 
     fn ownership_info_mut(&mut self) -> &mut AccountInfo {
-        self.accounts
-            .get_mut(&self.ownership)
+        self.account_info_mut(KeyedAccount::new(self.ownership))
             .expect(error::OWNERSHIP_NOT_FOUND)
     }
 
@@ -102,15 +451,17 @@ impl TokenState {
     fn transfer_ownership(&mut self, new_ownership: Account) {
         self.authorize_ownership();
 
+        let keyed = KeyedAccount::new(new_ownership);
+        assert!(!self.reserved(keyed), "{}", error::RESERVED_ADDRESS);
+
         let previous_ownership = self.ownership;
 
         self.ownership = new_ownership;
         // Always insert ownership
-        self.accounts
-            .entry(new_ownership)
-            .or_insert(AccountInfo::EMPTY);
+        self.account_entry(keyed);
+        self.prune_account(KeyedAccount::new(previous_ownership));
 
-        abi::emit(
+        self.emit(
             events::OwnershipTransferred::OWNERSHIP_TRANSFERRED,
             events::OwnershipTransferred {
                 previous_ownership,
@@ -124,8 +475,9 @@ impl TokenState {
 
         let previous_ownership = self.ownership;
         self.ownership = ZERO_ADDRESS;
+        self.prune_account(KeyedAccount::new(previous_ownership));
 
-        abi::emit(
+        self.emit(
             events::OwnershipTransferred::OWNERSHIP_RENOUNCED,
             events::OwnershipTransferred {
                 previous_ownership,
@@ -134,100 +486,176 @@ impl TokenState {
         );
     }
 
-    fn blocked(&self, account: Account) -> bool {
-        let ownership_account = self.accounts.get(&account);
+    fn is_reserved(&self, account: Account) -> bool {
+        self.reserved(KeyedAccount::new(account))
+    }
 
-        match ownership_account {
-            Some(account) => account.is_blocked(),
-            None => false,
-        }
+    /// Check if an account is reserved. Reserved accounts can never be
+    /// credited with tokens or receive the ownership.
+    fn reserved(&self, account: KeyedAccount) -> bool {
+        RESERVED_ADDRESSES.contains(&account.account)
+            || account.account == Account::from(abi::self_id())
+            || self.reserved_addresses.contains(&account.key)
+    }
+
+    /// Add a burn address to the reserved addresses.
+    fn add_reserved_address(&mut self, account: Account) {
+        self.authorize_ownership();
+
+        assert!(
+            account != self.ownership,
+            "The ownership can not be reserved"
+        );
+
+        self.reserved_addresses.insert(account_key(account));
+
+        self.emit(
+            events::ReservedAddressAdded::TOPIC,
+            events::ReservedAddressAdded { account },
+        );
+    }
+
+    fn blocked(&self, account: Account) -> bool {
+        self.account_info(KeyedAccount::new(account))
+            .is_some_and(AccountInfo::is_blocked)
     }
 
     fn frozen(&self, account: Account) -> bool {
-        let ownership_account = self.accounts.get(&account);
+        self.account_info(KeyedAccount::new(account))
+            .is_some_and(AccountInfo::is_frozen)
+    }
 
-        match ownership_account {
-            Some(account) => account.is_frozen(),
-            None => false,
-        }
+    /// Return the reason given for the active sanction on an account, if the
+    /// account is blocked or frozen.
+    fn sanction_reason(&self, account: Account) -> Option<Reason> {
+        self.sanction_reasons
+            .get(&account_key(account))
+            .map(|(_, reason)| *reason)
     }
 
-    fn block(&mut self, account: Account) {
+    fn block(&mut self, account: Account, reason: Reason) {
         self.authorize_ownership();
 
-        let account_info = self
-            .accounts
-            .get_mut(&account)
-            .expect(error::OWNERSHIP_NOT_FOUND);
+        let keyed = KeyedAccount::new(account);
+        let account_info = self.account_entry(keyed);
 
         account_info.block();
+        self.sanction_reasons.insert(keyed.key, (account, reason));
 
-        abi::emit(
+        self.emit(
             events::AccountStatus::BLOCKED_TOPIC,
-            events::AccountStatus::blocked(account),
+            events::AccountStatus::blocked(account, reason),
         );
     }
 
-    fn freeze(&mut self, account: Account) {
+    fn freeze(&mut self, account: Account, reason: Reason) {
         self.authorize_ownership();
 
-        let account_info = self
-            .accounts
-            .get_mut(&account)
-            .expect(error::OWNERSHIP_NOT_FOUND);
+        let keyed = KeyedAccount::new(account);
+        let account_info = self.account_entry(keyed);
 
         account_info.freeze();
+        self.sanction_reasons.insert(keyed.key, (account, reason));
 
-        abi::emit(
+        self.emit(
             events::AccountStatus::FROZEN_TOPIC,
-            events::AccountStatus::frozen(account),
+            events::AccountStatus::frozen(account, reason),
         );
     }
 
-    fn unblock(&mut self, account: Account) {
+    fn unblock(&mut self, account: Account, reason: Reason) {
         self.authorize_ownership();
 
+        let keyed = KeyedAccount::new(account);
         let account_info = self
-            .accounts
-            .get_mut(&account)
+            .account_info_mut(keyed)
             .expect(error::OWNERSHIP_NOT_FOUND);
 
         assert!(account_info.is_blocked(), "The account is not blocked");
 
         account_info.unblock();
+        self.sanction_reasons.remove(&keyed.key);
+        self.prune_account(keyed);
 
-        abi::emit(
+        self.emit(
             events::AccountStatus::UNBLOCKED_TOPIC,
-            events::AccountStatus::unblocked(account),
+            events::AccountStatus::unblocked(account, reason),
         );
     }
 
-    fn unfreeze(&mut self, account: Account) {
+    fn unfreeze(&mut self, account: Account, reason: Reason) {
         self.authorize_ownership();
 
+        let keyed = KeyedAccount::new(account);
         let account_info = self
-            .accounts
-            .get_mut(&account)
+            .account_info_mut(keyed)
             .expect(error::OWNERSHIP_NOT_FOUND);
 
         assert!(account_info.is_frozen(), "The account is not frozen");
 
         account_info.unfreeze();
+        self.sanction_reasons.remove(&keyed.key);
+        self.prune_account(keyed);
 
-        abi::emit(
+        self.emit(
             events::AccountStatus::UNFROZEN_TOPIC,
-            events::AccountStatus::unfrozen(account),
+            events::AccountStatus::unfrozen(account, reason),
         );
     }
 }
 
 /// Supply management implementation.
 impl TokenState {
-    fn mint(&mut self, receiver: Account, amount: u64) {
+    fn minter(&self) -> Option<Account> {
+        self.minter
+    }
+
+    /// Set the account allowed to mint besides the ownership, such as a
+    /// migration-contract. The minter can't call any other function
+    /// restricted to the ownership.
+    fn set_minter(&mut self, minter: Option<Account>) {
         self.authorize_ownership();
 
-        let receiver_account =
-            self.accounts.entry(receiver).or_insert(AccountInfo::EMPTY);
+        self.minter = minter;
+
+        self.emit(
+            events::MinterUpdated::TOPIC,
+            events::MinterUpdated { minter },
+        );
+    }
+
+    /// Give up the minter role, so that only the ownership can mint.
+    fn renounce_minter(&mut self) {
+        assert!(
+            Some(sender_account()) == self.minter,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+
+        self.minter = None;
+
+        self.emit(
+            events::MinterUpdated::TOPIC,
+            events::MinterUpdated { minter: None },
+        );
+    }
+
+    fn authorize_minting(&self) {
+        let sender = sender_account();
+        assert!(
+            sender == self.ownership || Some(sender) == self.minter,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+    }
+
+    fn mint(&mut self, receiver: Account, amount: u64, reason: Reason) {
+        assert_not_entered();
+        self.authorize_minting();
+        let keyed = KeyedAccount::new(receiver);
+        assert!(!self.reserved(keyed), "{}", error::RESERVED_ADDRESS);
+        self.check_reserves(amount);
+        self.check_compliance(ZERO_ADDRESS, receiver, amount);
 
         // Prevent overflow
         self.supply = if let Some(supply) = self.supply.checked_add(amount) {
@@ -235,26 +663,32 @@ impl TokenState {
         } else {
             panic!("{}", error::SUPPLY_OVERFLOW)
         };
+        self.minted += u128::from(amount);
 
+        let receiver_account = self.account_entry(keyed);
         receiver_account.balance += amount;
 
-        abi::emit(
+        self.emit_transfer_event(
             events::Transfer::MINT_TOPIC,
             events::Transfer {
                 sender: ZERO_ADDRESS,
                 spender: None,
                 receiver,
                 value: amount,
+                reason: Some(reason),
             },
         );
+        self.notify_compliance(ZERO_ADDRESS, receiver, amount);
     }
 
-    fn burn(&mut self, amount: u64) {
+    fn burn(&mut self, amount: u64, reason: Reason) {
+        assert_not_entered();
         self.authorize_ownership();
+        self.check_compliance(self.ownership, ZERO_ADDRESS, amount);
 
         let burn_account = self.ownership_info_mut();
 
-        if burn_account.balance < amount {
+        if burn_account.spendable() < amount {
             panic!("{}", error::BALANCE_TOO_LOW);
         } else {
             burn_account.balance -= amount;
@@ -262,167 +696,2133 @@ impl TokenState {
 
         // this can never fail, as the balance is checked above
         self.supply -= amount;
+        self.burned += u128::from(amount);
 
-        abi::emit(
+        self.emit_transfer_event(
             events::Transfer::BURN_TOPIC,
             events::Transfer {
                 sender: self.ownership,
                 spender: None,
                 receiver: ZERO_ADDRESS,
                 value: amount,
+                reason: Some(reason),
             },
         );
-    }
-}
-
-/// Administrative functions.
-impl TokenState {
-    fn is_paused(&self) -> bool {
-        self.is_paused
-    }
-
-    fn toggle_pause(&mut self) {
-        self.authorize_ownership();
-
-        self.is_paused = !self.is_paused;
-
-        abi::emit(
-            events::PauseToggled::TOPIC,
-            events::PauseToggled {
-                paused: self.is_paused,
-            },
-        );
+        self.notify_compliance(self.ownership, ZERO_ADDRESS, amount);
     }
 
-    /// note: this function will fail if the balance of the obliged sender is
-    /// too low. It will **not** default to the maximum available balance.
-    fn force_transfer(
+    /// Burn tokens directly from any account, including blocked and frozen
+    /// ones.
+    ///
+    /// The compliance contract can't veto the burn, but is notified of it.
+    ///
+    /// note: this function will fail if the balance of the account is too
+    /// low. It will **not** default to the maximum available balance.
+    fn controller_redeem(
         &mut self,
-        obliged_sender: Account,
-        receiver: Account,
+        account: Account,
         value: u64,
+        reason: Reason,
     ) {
+        assert_not_entered();
         self.authorize_ownership();
 
-        let obliged_sender_account = self
-            .accounts
-            .get_mut(&obliged_sender)
+        let keyed = KeyedAccount::new(account);
+        let account_info = self
+            .account_info_mut(keyed)
             .expect(error::ACCOUNT_NOT_FOUND);
 
         assert!(
-            obliged_sender_account.balance >= value,
+            account_info.spendable() >= value,
             "{}",
             error::BALANCE_TOO_LOW
         );
 
-        obliged_sender_account.balance -= value;
-
-        let receiver_account =
-            self.accounts.entry(receiver).or_insert(AccountInfo::EMPTY);
+        account_info.balance -= value;
+        self.prune_account(keyed);
 
-        // this can never overflow as value + balance is never higher than total
-        // supply
-        receiver_account.balance += value;
+        // this can never fail, as the balance is checked above
+        self.supply -= value;
+        self.burned += u128::from(value);
 
-        abi::emit(
-            events::Transfer::FORCE_TRANSFER_TOPIC,
+        self.emit_transfer_event(
+            events::Transfer::CONTROLLER_REDEEM_TOPIC,
             events::Transfer {
-                sender: obliged_sender,
+                sender: account,
                 spender: None,
-                receiver,
+                receiver: ZERO_ADDRESS,
                 value,
+                reason: Some(reason),
             },
         );
+        self.notify_compliance(account, ZERO_ADDRESS, value);
     }
 }
 
-/// Basic token-contract implementation.
+/// Redemption implementation.
 impl TokenState {
-    fn name() -> String {
-        String::from("Electronic Money Token")
-    }
-
-    fn symbol() -> String {
-        String::from("EMT")
-    }
-
-    fn decimals() -> u8 {
-        18
-    }
-
-    fn total_supply(&self) -> u64 {
-        self.supply
-    }
-
-    fn account(&self, account: Account) -> AccountInfo {
-        self.accounts
-            .get(&account)
-            .copied()
-            .unwrap_or(AccountInfo::EMPTY)
-    }
-
-    fn balance_of(&self, account: Account) -> u64 {
-        match self.accounts.get(&account) {
-            Some(account_info) => account_info.balance,
-            None => 0,
-        }
-    }
-
-    #[allow(clippy::large_types_passed_by_value)]
-    fn allowance(&self, owner: Account, spender: Account) -> u64 {
-        match self.allowances.get(&owner) {
-            Some(allowances) => allowances.get(&spender).copied().unwrap_or(0),
-            None => 0,
-        }
+    fn pending_redemptions(&self) -> Vec<(u64, RedemptionRequest)> {
+        self.redemptions
+            .iter()
+            .map(|(id, request)| (*id, *request))
+            .collect()
     }
 
-    /// Initates a `Transfer` from the sender to the receiver with the specified
-    /// value.
+    /// Request the redemption of `value` tokens at par. The tokens are taken
+    /// from the sender and held in escrow by the contract until the request
+    /// is settled or rejected by the ownership.
     ///
-    /// Both the sender and the receiver are accounts.
+    /// Returns the id of the redemption request.
     ///
     /// # Note
     /// the sender must not be blocked or frozen.
-    /// the receiver must not be blocked but can be frozen.
-    #[allow(clippy::large_types_passed_by_value)]
-    fn transfer(&mut self, receiver: Account, value: u64) {
+    fn request_redemption(
+        &mut self,
+        value: u64,
+        payout_reference: [u8; 32],
+    ) -> u64 {
+        assert_not_entered();
         assert!(!self.is_paused, "{}", error::PAUSED_MESSAGE);
+        assert!(value > 0, "The redemption value must not be zero");
 
-        let sender = sender_account();
+        let holder = sender_account();
+        self.check_compliance(holder, ZERO_ADDRESS, value);
 
-        let sender_account = self
-            .accounts
-            .get_mut(&sender)
+        let keyed = KeyedAccount::new(holder);
+        let holder_account = self
+            .account_info_mut(keyed)
             .expect(error::ACCOUNT_NOT_FOUND);
-        assert!(!sender_account.is_blocked(), "{}", error::BLOCKED);
-        assert!(!sender_account.is_frozen(), "{}", error::FROZEN);
+        assert!(!holder_account.is_blocked(), "{}", error::BLOCKED);
+        assert!(!holder_account.is_frozen(), "{}", error::FROZEN);
 
         assert!(
-            sender_account.balance >= value,
+            holder_account.spendable() >= value,
             "{}",
             error::BALANCE_TOO_LOW
         );
 
-        sender_account.balance -= value;
+        holder_account.balance -= value;
+        self.prune_account(keyed);
 
-        let receiver_account =
-            self.accounts.entry(receiver).or_insert(AccountInfo::EMPTY);
+        let id = self.next_redemption_id;
+        self.next_redemption_id += 1;
 
-        assert!(!receiver_account.is_blocked(), "{}", error::BLOCKED);
+        self.redemptions.insert(
+            id,
+            RedemptionRequest {
+                holder,
+                value,
+                payout_reference,
+            },
+        );
 
-        // this can never overflow as value + balance is never higher than total
-        // supply
-        receiver_account.balance += value;
+        self.emit(
+            events::RedemptionRequested::TOPIC,
+            events::RedemptionRequested {
+                id,
+                holder,
+                value,
+                payout_reference,
+            },
+        );
 
-        abi::emit(
-            events::Transfer::TRANSFER_TOPIC,
+        id
+    }
+
+    /// Settle a pending redemption request by burning the escrowed tokens.
+    /// The `settlement_reference` documents the payout made to the holder.
+    fn settle_redemption(&mut self, id: u64, settlement_reference: [u8; 32]) {
+        assert_not_entered();
+        self.authorize_ownership();
+
+        let request = self
+            .redemptions
+            .remove(&id)
+            .expect(error::REDEMPTION_NOT_FOUND);
+
+        // this can never fail, as the escrowed value is part of the supply
+        self.supply -= request.value;
+        self.burned += u128::from(request.value);
+
+        self.emit(
+            events::RedemptionSettled::TOPIC,
+            events::RedemptionSettled {
+                id,
+                holder: request.holder,
+                value: request.value,
+                settlement_reference,
+            },
+        );
+        self.emit_transfer_event(
+            events::Transfer::BURN_TOPIC,
             events::Transfer {
-                sender,
+                sender: request.holder,
                 spender: None,
-                receiver,
-                value,
+                receiver: ZERO_ADDRESS,
+                value: request.value,
+                reason: None,
             },
         );
+        self.notify_compliance(request.holder, ZERO_ADDRESS, request.value);
+    }
+
+    /// Reject a pending redemption request by returning the escrowed tokens
+    /// to the holder.
+    fn reject_redemption(&mut self, id: u64, reason: Reason) {
+        assert_not_entered();
+        self.authorize_ownership();
+
+        let request = self
+            .redemptions
+            .remove(&id)
+            .expect(error::REDEMPTION_NOT_FOUND);
+
+        let holder_account =
+            self.account_entry(KeyedAccount::new(request.holder));
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        holder_account.balance += request.value;
+
+        self.emit(
+            events::RedemptionRejected::TOPIC,
+            events::RedemptionRejected {
+                id,
+                holder: request.holder,
+                value: request.value,
+                reason,
+            },
+        );
+    }
+}
+
+/// Hold implementation.
+impl TokenState {
+    fn hold_data(&self, id: u64) -> Option<Hold> {
+        self.holds.get(&id).copied()
+    }
+
+    fn balance_on_hold(&self, account: Account) -> u64 {
+        match self.account_info(KeyedAccount::new(account)) {
+            Some(account_info) => account_info.held,
+            None => 0,
+        }
+    }
+
+    /// Put `value` of the sender's balance on hold for the `payee`. The
+    /// `notary` decides whether the hold is executed or released.
+    ///
+    /// Returns the id of the hold.
+    ///
+    /// # Note
+    /// the sender must not be blocked or frozen.
+    #[allow(clippy::similar_names)]
+    fn hold(
+        &mut self,
+        payee: Account,
+        notary: Account,
+        value: u64,
+        expiration: u64,
+    ) -> u64 {
+        assert_not_entered();
+        let payer = sender_account();
+        self.put_on_hold(payer, payee, notary, value, expiration)
+    }
+
+    /// Put `value` of the payer's balance on hold for the `payee` on behalf
+    /// of the payer.
+    ///
+    /// Returns the id of the hold.
+    ///
+    /// # Note
+    /// only the ownership and the operators of the payer can put its balance
+    /// on hold.
+    /// the payer must not be blocked or frozen.
+    #[allow(clippy::similar_names)]
+    fn hold_from(
+        &mut self,
+        payer: Account,
+        payee: Account,
+        notary: Account,
+        value: u64,
+        expiration: u64,
+    ) -> u64 {
+        assert_not_entered();
+        let sender = sender_account();
+        assert!(
+            sender == self.ownership || self.is_operator_for(sender, payer),
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+        self.put_on_hold(payer, payee, notary, value, expiration)
+    }
+
+    #[allow(clippy::similar_names)]
+    fn put_on_hold(
+        &mut self,
+        payer: Account,
+        payee: Account,
+        notary: Account,
+        value: u64,
+        expiration: u64,
+    ) -> u64 {
+        assert!(!self.is_paused, "{}", error::PAUSED_MESSAGE);
+        assert!(
+            expiration == 0 || expiration > abi::block_height(),
+            "The hold expiration must be in the future"
+        );
+        assert!(!self.is_reserved(payee), "{}", error::RESERVED_ADDRESS);
+
+        let payer_account = self
+            .account_info_mut(KeyedAccount::new(payer))
+            .expect(error::ACCOUNT_NOT_FOUND);
+        assert!(!payer_account.is_blocked(), "{}", error::BLOCKED);
+        assert!(!payer_account.is_frozen(), "{}", error::FROZEN);
+
+        assert!(
+            payer_account.spendable() >= value,
+            "{}",
+            error::BALANCE_TOO_LOW
+        );
+
+        payer_account.held += value;
+
+        let id = self.next_hold_id;
+        self.next_hold_id += 1;
+
+        self.holds.insert(
+            id,
+            Hold {
+                payer,
+                payee,
+                notary,
+                value,
+                expiration,
+            },
+        );
+
+        self.emit(
+            events::HoldCreated::TOPIC,
+            events::HoldCreated {
+                id,
+                payer,
+                payee,
+                notary,
+                value,
+                expiration,
+            },
+        );
+
+        id
+    }
+
+    /// Execute a hold by transferring `value` from the payer to the payee.
+    /// Any value on hold exceeding `value` is released.
+    ///
+    /// # Note
+    /// only the notary of the hold can execute it and only before it expires.
+    /// the payer must not be blocked or frozen.
+    /// the payee must not be blocked but can be frozen.
+    /// the jurisdictions of the payer and the payee must not be restricted.
+    #[allow(clippy::similar_names)]
+    fn execute_hold(&mut self, id: u64, value: u64) {
+        assert_not_entered();
+        assert!(!self.is_paused, "{}", error::PAUSED_MESSAGE);
+
+        let hold = self.holds.remove(&id).expect(error::HOLD_NOT_FOUND);
+
+        assert!(
+            sender_account() == hold.notary,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+        assert!(
+            !hold.is_expired(abi::block_height()),
+            "The hold has expired"
+        );
+        assert!(value <= hold.value, "The value exceeds the value on hold");
+
+        let payer = KeyedAccount::new(hold.payer);
+        let payee = KeyedAccount::new(hold.payee);
+        assert!(
+            self.jurisdictions_allowed(payer, payee),
+            "{}",
+            error::JURISDICTION_RESTRICTED
+        );
+        self.check_compliance(hold.payer, hold.payee, value);
+
+        let payer_account = self
+            .account_info_mut(payer)
+            .expect(error::ACCOUNT_NOT_FOUND);
+        assert!(!payer_account.is_blocked(), "{}", error::BLOCKED);
+        assert!(!payer_account.is_frozen(), "{}", error::FROZEN);
+
+        payer_account.held -= hold.value;
+        payer_account.balance -= value;
+
+        assert!(!self.reserved(payee), "{}", error::RESERVED_ADDRESS);
+
+        let payee_account = self.account_entry(payee);
+        assert!(!payee_account.is_blocked(), "{}", error::BLOCKED);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        payee_account.balance += value;
+
+        self.prune_account(payer);
+
+        self.emit(
+            events::HoldExecuted::TOPIC,
+            events::HoldExecuted {
+                id,
+                payer: hold.payer,
+                payee: hold.payee,
+                value,
+            },
+        );
+        self.emit_transfer(hold.payer, None, hold.payee, value, None);
+        self.notify_compliance(hold.payer, hold.payee, value);
+    }
+
+    /// Release a hold, making the value on hold spendable by the payer again.
+    ///
+    /// # Note
+    /// the notary and the payee can release the hold at any time, the payer
+    /// only once it has expired.
+    fn release_hold(&mut self, id: u64) {
+        assert_not_entered();
+        let hold = self.holds.remove(&id).expect(error::HOLD_NOT_FOUND);
+
+        let sender = sender_account();
+        assert!(
+            sender == hold.notary
+                || sender == hold.payee
+                || (sender == hold.payer
+                    && hold.is_expired(abi::block_height())),
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+
+        let payer_account = self
+            .account_info_mut(KeyedAccount::new(hold.payer))
+            .expect(error::ACCOUNT_NOT_FOUND);
+
+        payer_account.held -= hold.value;
+
+        self.emit(
+            events::HoldReleased::TOPIC,
+            events::HoldReleased {
+                id,
+                payer: hold.payer,
+                value: hold.value,
+            },
+        );
+    }
+}
+
+/// Clearing implementation.
+impl TokenState {
+    fn clearing_threshold(&self) -> Option<u64> {
+        self.clearing_threshold
+    }
+
+    fn clearing_agent(&self) -> Option<Account> {
+        self.clearing_agent
+    }
+
+    fn pending_transfers(&self) -> Vec<(u64, PendingTransfer)> {
+        self.pending_transfers
+            .iter()
+            .map(|(id, pending)| (*id, pending.clone()))
+            .collect()
+    }
+
+    /// Set the value above which transfers are queued for clearing and the
+    /// account allowed to clear them besides the ownership. A `threshold` of
+    /// `None` disables clearing for new transfers.
+    fn set_clearing(&mut self, threshold: Option<u64>, agent: Option<Account>) {
+        self.authorize_ownership();
+
+        self.clearing_threshold = threshold;
+        self.clearing_agent = agent;
+
+        self.emit(
+            events::ClearingUpdated::TOPIC,
+            events::ClearingUpdated { threshold, agent },
+        );
+    }
+
+    fn authorize_clearing(&self) {
+        let sender = sender_account();
+        assert!(
+            sender == self.ownership || Some(sender) == self.clearing_agent,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+    }
+
+    fn requires_clearing(&self, value: u64) -> bool {
+        self.clearing_threshold
+            .is_some_and(|threshold| value > threshold)
+    }
+
+    /// Queue a transfer whose value has already been taken from the sender,
+    /// and from the allowance of the spender if `from_allowance` is set.
+    #[allow(clippy::similar_names)]
+    fn queue_transfer(
+        &mut self,
+        sender: Account,
+        spender: Option<Account>,
+        from_allowance: bool,
+        receiver: KeyedAccount,
+        value: u64,
+        memo: Option<Vec<u8>>,
+    ) {
+        assert!(
+            !self
+                .account_info(receiver)
+                .is_some_and(AccountInfo::is_blocked),
+            "{}",
+            error::BLOCKED
+        );
+
+        let receiver = receiver.account;
+        let id = self.next_pending_transfer_id;
+        self.next_pending_transfer_id += 1;
+
+        self.pending_transfers.insert(
+            id,
+            PendingTransfer {
+                sender,
+                spender,
+                from_allowance,
+                receiver,
+                value,
+                memo,
+            },
+        );
+
+        self.emit(
+            events::TransferQueued::TOPIC,
+            events::TransferQueued {
+                id,
+                sender,
+                spender,
+                receiver,
+                value,
+            },
+        );
+    }
+
+    /// Approve a pending transfer by crediting the escrowed tokens to the
+    /// receiver.
+    ///
+    /// # Note
+    /// the receiver must not be blocked but can be frozen.
+    fn approve_transfer(&mut self, id: u64) {
+        assert_not_entered();
+        assert!(!self.is_paused, "{}", error::PAUSED_MESSAGE);
+        self.authorize_clearing();
+
+        let pending = self
+            .pending_transfers
+            .remove(&id)
+            .expect(error::PENDING_TRANSFER_NOT_FOUND);
+
+        let sender = KeyedAccount::new(pending.sender);
+        let receiver = KeyedAccount::new(pending.receiver);
+        assert!(!self.reserved(receiver), "{}", error::RESERVED_ADDRESS);
+        assert!(
+            self.jurisdictions_allowed(sender, receiver),
+            "{}",
+            error::JURISDICTION_RESTRICTED
+        );
+        self.check_compliance(pending.sender, pending.receiver, pending.value);
+
+        let receiver_account = self.account_entry(receiver);
+        assert!(!receiver_account.is_blocked(), "{}", error::BLOCKED);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        receiver_account.balance += pending.value;
+
+        self.emit(
+            events::TransferCleared::APPROVED_TOPIC,
+            events::TransferCleared {
+                id,
+                sender: pending.sender,
+                receiver: pending.receiver,
+                value: pending.value,
+                reason: None,
+            },
+        );
+        self.emit_transfer(
+            pending.sender,
+            pending.spender,
+            pending.receiver,
+            pending.value,
+            pending.memo,
+        );
+        self.notify_compliance(pending.sender, pending.receiver, pending.value);
+    }
+
+    /// Reject a pending transfer by returning the escrowed tokens to the
+    /// sender.
+    fn reject_transfer(&mut self, id: u64, reason: Reason) {
+        assert_not_entered();
+        self.authorize_clearing();
+
+        let pending = self
+            .pending_transfers
+            .remove(&id)
+            .expect(error::PENDING_TRANSFER_NOT_FOUND);
+        self.return_pending(&pending);
+
+        self.emit(
+            events::TransferCleared::REJECTED_TOPIC,
+            events::TransferCleared {
+                id,
+                sender: pending.sender,
+                receiver: pending.receiver,
+                value: pending.value,
+                reason: Some(reason),
+            },
+        );
+    }
+
+    /// Cancel a pending transfer by returning the escrowed tokens to the
+    /// sender.
+    ///
+    /// # Note
+    /// only the sender, or the spender that initiated the transfer, can
+    /// cancel it.
+    fn cancel_transfer(&mut self, id: u64) {
+        assert_not_entered();
+        let pending = self
+            .pending_transfers
+            .remove(&id)
+            .expect(error::PENDING_TRANSFER_NOT_FOUND);
+
+        let sender = sender_account();
+        assert!(
+            sender == pending.sender || Some(sender) == pending.spender,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+
+        self.return_pending(&pending);
+
+        self.emit(
+            events::TransferCleared::CANCELLED_TOPIC,
+            events::TransferCleared {
+                id,
+                sender: pending.sender,
+                receiver: pending.receiver,
+                value: pending.value,
+                reason: None,
+            },
+        );
+    }
+
+    /// Return the escrowed tokens of a pending transfer to the sender and
+    /// restore the allowance of the spender, if any.
+    fn return_pending(&mut self, pending: &PendingTransfer) {
+        let keyed_sender = KeyedAccount::new(pending.sender);
+        let sender_account = self.account_entry(keyed_sender);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        sender_account.balance += pending.value;
+
+        let spender = pending.spender.filter(|_| pending.from_allowance);
+        if let Some(spender) = spender {
+            let spender = KeyedAccount::new(spender);
+            let allowance = self.allowance_entry(keyed_sender, spender);
+            *allowance = allowance.saturating_add(pending.value);
+        }
+    }
+}
+
+/// Compliance implementation.
+impl TokenState {
+    fn compliance(&self) -> Option<ContractId> {
+        self.compliance
+    }
+
+    /// Register the contract checking every transfer, mint and burn, except
+    /// for the administrative actions it can't veto. A `compliance` of `None`
+    /// removes the current compliance contract.
+    fn set_compliance(&mut self, compliance: Option<ContractId>) {
+        self.authorize_ownership();
+
+        self.compliance = compliance;
+
+        self.emit(
+            events::ComplianceUpdated::TOPIC,
+            events::ComplianceUpdated { compliance },
+        );
+    }
+
+    /// Ask the compliance contract, if any, whether the operation is allowed.
+    ///
+    /// # Panics
+    /// If the call to the compliance contract fails.
+    fn complies(&self, sender: Account, receiver: Account, value: u64) -> bool {
+        let Some(compliance) = self.compliance else {
+            return true;
+        };
+
+        abi::call(
+            compliance,
+            compliance::CAN_TRANSFER,
+            &(sender, receiver, value),
+        )
+        .unwrap_or_else(|err| {
+            panic!(
+                "Failed calling `{}` on the compliance contract: {err}",
+                compliance::CAN_TRANSFER
+            )
+        })
+    }
+
+    fn check_compliance(&self, sender: Account, receiver: Account, value: u64) {
+        assert!(
+            self.complies(sender, receiver, value),
+            "{}",
+            error::COMPLIANCE_REJECTED
+        );
+    }
+
+    /// Notify the compliance contract, if any, of an executed operation.
+    ///
+    /// The balances tree is brought up to date first, since the compliance
+    /// contract may query it.
+    fn notify_compliance(
+        &mut self,
+        sender: Account,
+        receiver: Account,
+        value: u64,
+    ) {
+        let Some(compliance) = self.compliance else {
+            return;
+        };
+        self.update_balances_tree();
+
+        if let Err(err) = abi::call::<_, ()>(
+            compliance,
+            compliance::TRANSFERRED,
+            &(sender, receiver, value),
+        ) {
+            panic!(
+                "Failed calling `{}` on the compliance contract: {err}",
+                compliance::TRANSFERRED
+            );
+        }
+    }
+}
+
+/// Attribute implementation.
+impl TokenState {
+    fn attributes(&self, account: Account) -> AccountAttributes {
+        self.account_attributes(KeyedAccount::new(account))
+    }
+
+    fn account_attributes(&self, account: KeyedAccount) -> AccountAttributes {
+        self.attributes
+            .get(&account.key)
+            .copied()
+            .unwrap_or(AccountAttributes::EMPTY)
+    }
+
+    fn attribute_manager(&self) -> Option<Account> {
+        self.attribute_manager
+    }
+
+    /// Set the account allowed to set account attributes besides the
+    /// ownership.
+    fn set_attribute_manager(&mut self, manager: Option<Account>) {
+        self.authorize_ownership();
+
+        self.attribute_manager = manager;
+
+        self.emit(
+            events::AttributeManagerUpdated::TOPIC,
+            events::AttributeManagerUpdated { manager },
+        );
+    }
+
+    /// Replace the attributes of an account. Setting empty attributes removes
+    /// the account from the attribute store.
+    fn set_attributes(
+        &mut self,
+        account: Account,
+        attributes: AccountAttributes,
+    ) {
+        let sender = sender_account();
+        assert!(
+            sender == self.ownership || Some(sender) == self.attribute_manager,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+
+        if attributes.is_empty() {
+            self.attributes.remove(&account_key(account));
+        } else {
+            self.attributes.insert(account_key(account), attributes);
+        }
+
+        self.emit(
+            events::AttributesUpdated::TOPIC,
+            events::AttributesUpdated {
+                account,
+                attributes,
+            },
+        );
+    }
+
+    fn jurisdictions_restricted(
+        &self,
+        sender: Jurisdiction,
+        receiver: Jurisdiction,
+    ) -> bool {
+        self.restricted_jurisdictions.contains(&(sender, receiver))
+    }
+
+    /// Restrict transfers from accounts of the `sender` jurisdiction to
+    /// accounts of the `receiver` jurisdiction.
+    fn restrict_jurisdictions(
+        &mut self,
+        sender: Jurisdiction,
+        receiver: Jurisdiction,
+    ) {
+        self.authorize_ownership();
+
+        self.restricted_jurisdictions.insert((sender, receiver));
+
+        self.emit(
+            events::JurisdictionRestriction::RESTRICTED_TOPIC,
+            events::JurisdictionRestriction { sender, receiver },
+        );
+    }
+
+    fn unrestrict_jurisdictions(
+        &mut self,
+        sender: Jurisdiction,
+        receiver: Jurisdiction,
+    ) {
+        self.authorize_ownership();
+
+        assert!(
+            self.restricted_jurisdictions.remove(&(sender, receiver)),
+            "The jurisdictions are not restricted"
+        );
+
+        self.emit(
+            events::JurisdictionRestriction::UNRESTRICTED_TOPIC,
+            events::JurisdictionRestriction { sender, receiver },
+        );
+    }
+
+    /// Check that transfers between the jurisdictions of the accounts are not
+    /// restricted. Accounts without a jurisdiction are never restricted.
+    fn jurisdictions_allowed(
+        &self,
+        sender: KeyedAccount,
+        receiver: KeyedAccount,
+    ) -> bool {
+        let sender = self.account_attributes(sender).jurisdiction;
+        let receiver = self.account_attributes(receiver).jurisdiction;
+
+        match (sender, receiver) {
+            (Some(sender), Some(receiver)) => {
+                !self.jurisdictions_restricted(sender, receiver)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Reserve attestation implementation.
+impl TokenState {
+    fn attestor(&self) -> Option<Account> {
+        self.attestor
+    }
+
+    fn attestation_validity(&self) -> Option<u64> {
+        self.attestation_validity
+    }
+
+    fn reserve_attestation(&self) -> Option<ReserveAttestation> {
+        self.reserve_attestation
+    }
+
+    /// Set the account publishing reserve attestations and the number of
+    /// blocks an attestation stays valid for. While an attestor is set,
+    /// minting is gated by its latest attestation. An `attestor` of `None`
+    /// lifts the gate.
+    fn set_attestor(
+        &mut self,
+        attestor: Option<Account>,
+        validity: Option<u64>,
+    ) {
+        self.authorize_ownership();
+
+        self.attestor = attestor;
+        self.attestation_validity = validity;
+
+        self.emit(
+            events::AttestorUpdated::TOPIC,
+            events::AttestorUpdated { attestor, validity },
+        );
+    }
+
+    /// Publish an attestation of the reserves backing the token, replacing
+    /// the previous one.
+    fn attest_reserves(
+        &mut self,
+        reserves: u64,
+        timestamp: u64,
+        report_hash: [u8; 32],
+    ) {
+        let attestor = sender_account();
+        assert!(
+            Some(attestor) == self.attestor,
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+
+        if let Some(latest) = self.reserve_attestation {
+            assert!(
+                timestamp > latest.timestamp,
+                "{}",
+                error::ATTESTATION_OUTDATED
+            );
+        }
+
+        let attestation = ReserveAttestation {
+            reserves,
+            timestamp,
+            report_hash,
+            block_height: abi::block_height(),
+        };
+        self.reserve_attestation = Some(attestation);
+
+        self.emit(
+            events::ReservesAttested::TOPIC,
+            events::ReservesAttested {
+                attestor,
+                attestation,
+            },
+        );
+    }
+
+    /// Check that minting `amount` keeps the supply covered by the latest
+    /// reserve attestation, if minting is gated by attestations.
+    fn check_reserves(&self, amount: u64) {
+        if self.attestor.is_none() {
+            return;
+        }
+
+        let attestation = self
+            .reserve_attestation
+            .expect(error::ATTESTATION_NOT_FOUND);
+
+        if let Some(validity) = self.attestation_validity {
+            assert!(
+                !attestation.is_stale(abi::block_height(), validity),
+                "{}",
+                error::ATTESTATION_STALE
+            );
+        }
+
+        assert!(
+            self.supply.saturating_add(amount) <= attestation.reserves,
+            "{}",
+            error::RESERVES_EXCEEDED
+        );
+    }
+}
+
+/// Document implementation.
+impl TokenState {
+    fn document(&self, name: &str) -> Option<Document> {
+        self.documents.get(name).cloned()
+    }
+
+    fn documents(&self) -> Vec<(String, Document)> {
+        self.documents
+            .iter()
+            .map(|(name, document)| (name.clone(), document.clone()))
+            .collect()
+    }
+
+    /// Register a document under a name, replacing any document previously
+    /// registered under it.
+    ///
+    /// # Panics
+    /// This function will panic if the name or the URI exceed
+    /// [`Document::MAX_NAME_SIZE`] and [`Document::MAX_URI_SIZE`].
+    fn set_document(&mut self, name: String, uri: String, hash: [u8; 32]) {
+        self.authorize_ownership();
+
+        assert!(
+            name.len() <= Document::MAX_NAME_SIZE
+                && uri.len() <= Document::MAX_URI_SIZE,
+            "{}",
+            error::DOCUMENT_TOO_LARGE
+        );
+
+        let document = Document {
+            uri,
+            hash,
+            modified: abi::block_height(),
+        };
+        self.documents.insert(name.clone(), document.clone());
+
+        self.emit(
+            events::DocumentUpdated::TOPIC,
+            events::DocumentUpdated { name, document },
+        );
+    }
+
+    fn remove_document(&mut self, name: String) {
+        self.authorize_ownership();
+
+        let document = self
+            .documents
+            .remove(&name)
+            .expect(error::DOCUMENT_NOT_FOUND);
+
+        self.emit(
+            events::DocumentUpdated::REMOVED_TOPIC,
+            events::DocumentUpdated { name, document },
+        );
+    }
+}
+
+/// State maintenance implementation.
+impl TokenState {
+    /// Remove the entry of an account that holds no tokens, has no status, no
+    /// allowances and no operators, to bound the growth of the state. The
+    /// ownership is always kept.
+    fn prune_account(&mut self, account: KeyedAccount) {
+        let is_empty = self
+            .account_info(account)
+            .is_some_and(AccountInfo::is_empty);
+
+        if is_empty
+            && account.account != self.ownership
+            && !self.has_allowances(account)
+            && !self.has_operators(account)
+        {
+            self.accounts.remove(&account.key);
+            self.mark_modified(account.key);
+        }
+    }
+
+    /// Remove a spent allowance.
+    fn prune_allowance(&mut self, owner: KeyedAccount, spender: KeyedAccount) {
+        let key = (owner.key, spender.key);
+        if self
+            .allowances
+            .get(&key)
+            .is_some_and(|(_, value)| *value == 0)
+        {
+            self.allowances.remove(&key);
+        }
+    }
+
+    /// Remove the empty entries and spent allowances of up to `limit`
+    /// accounts, in storage order starting after the account `after`, or from
+    /// the first account if `None`.
+    ///
+    /// Returns the number of account entries removed and the last account
+    /// processed, to pass as `after` for the next page, or `None` if no
+    /// accounts were left to process.
+    fn compact_accounts(
+        &mut self,
+        after: Option<Account>,
+        limit: u32,
+    ) -> (u64, Option<Account>) {
+        self.authorize_ownership();
+
+        let page: Vec<KeyedAccount> = self
+            .accounts_after(after)
+            .take(limit as usize)
+            .map(|(key, (account, _))| KeyedAccount {
+                account: *account,
+                key: *key,
+            })
+            .collect();
+
+        let entries = self.accounts.len();
+        for account in &page {
+            let spent: Vec<_> = entries_of(&self.allowances, account.key)
+                .filter(|(_, (_, value))| *value == 0)
+                .map(|(key, _)| *key)
+                .collect();
+            for key in spent {
+                self.allowances.remove(&key);
+            }
+
+            self.prune_account(*account);
+        }
+
+        let removed = (entries - self.accounts.len()) as u64;
+        (removed, page.last().map(|account| account.account))
+    }
+}
+
+/// Audit implementation.
+impl TokenState {
+    /// Return the tokens held in escrow for pending redemptions and transfers
+    /// pending clearing.
+    fn escrowed(&self) -> u64 {
+        let redemptions =
+            self.redemptions.values().map(|request| request.value);
+        let transfers =
+            self.pending_transfers.values().map(|pending| pending.value);
+
+        redemptions.chain(transfers).fold(0, u64::saturating_add)
+    }
+
+    /// Break the supply down by who holds the tokens.
+    ///
+    /// The frozen and blocked balances are summed over the accounts with a
+    /// recorded sanction reason, rather than over all accounts. A sanctioned
+    /// account always has one: `block` and `freeze` record the reason along
+    /// with the status, `unblock` and `unfreeze` remove it, and `import_state`
+    /// rejects a status without a reason.
+    fn supply_breakdown(&self) -> SupplyBreakdown {
+        let ownership = self
+            .account_info(KeyedAccount::new(self.ownership))
+            .map_or(0, |account_info| account_info.balance);
+
+        let mut frozen = 0u64;
+        let mut blocked = 0u64;
+        for (key, (account, _)) in &self.sanction_reasons {
+            if *account == self.ownership {
+                continue;
+            }
+            match self.accounts.get(key).map(|(_, account_info)| account_info) {
+                Some(account_info) if account_info.is_frozen() => {
+                    frozen = frozen.saturating_add(account_info.balance);
+                }
+                Some(account_info) if account_info.is_blocked() => {
+                    blocked = blocked.saturating_add(account_info.balance);
+                }
+                _ => {}
+            }
+        }
+
+        let escrowed = self.escrowed();
+        let circulating = [ownership, frozen, blocked, escrowed]
+            .into_iter()
+            .fold(self.supply, u64::saturating_sub);
+
+        SupplyBreakdown {
+            supply: self.supply,
+            minted: self.minted,
+            burned: self.burned,
+            ownership,
+            frozen,
+            blocked,
+            escrowed,
+            circulating,
+        }
+    }
+
+    /// Check the invariants of up to `limit` accounts, in storage order
+    /// starting after the account `after`, or from the first account if
+    /// `None`. `balances` is the sum of the balances reported by the previous
+    /// page, or zero for the first page.
+    ///
+    /// Violations are reported instead of panicking. The invariants over the
+    /// whole state are checked once the last account has been checked.
+    fn verify_invariants(
+        &self,
+        after: Option<Account>,
+        balances: u64,
+        limit: u32,
+    ) -> InvariantReport {
+        let mut report = InvariantReport {
+            last: after,
+            balances,
+            complete: false,
+            violations: Vec::new(),
+        };
+
+        let mut accounts = self.accounts_after(after);
+        for (key, (account, account_info)) in
+            accounts.by_ref().take(limit as usize)
+        {
+            let account = *account;
+            report.last = Some(account);
+            report.balances =
+                report.balances.saturating_add(account_info.balance);
+
+            if account_info.held > account_info.balance {
+                report.violations.push(
+                    InvariantViolation::HeldExceedsBalance {
+                        account,
+                        balance: account_info.balance,
+                        held: account_info.held,
+                    },
+                );
+            }
+            match account_info.status {
+                AccountInfo::NO_STATUS => {}
+                AccountInfo::FROZEN | AccountInfo::BLOCKED => {
+                    if !self.sanction_reasons.contains_key(key) {
+                        report.violations.push(
+                            InvariantViolation::UnrecordedSanction { account },
+                        );
+                    }
+                }
+                status => {
+                    report.violations.push(InvariantViolation::UnknownStatus {
+                        account,
+                        status,
+                    });
+                }
+            }
+        }
+        report.complete = accounts.next().is_none();
+
+        if report.complete {
+            for (key, (account, _)) in &self.sanction_reasons {
+                let is_sanctioned =
+                    self.accounts.get(key).is_some_and(|(_, account_info)| {
+                        account_info.is_frozen() || account_info.is_blocked()
+                    });
+                if !is_sanctioned {
+                    report.violations.push(InvariantViolation::StaleSanction {
+                        account: *account,
+                    });
+                }
+            }
+
+            if self.minted.checked_sub(self.burned)
+                != Some(u128::from(self.supply))
+            {
+                report.violations.push(InvariantViolation::SupplyMismatch {
+                    supply: self.supply,
+                    minted: self.minted,
+                    burned: self.burned,
+                });
+            }
+
+            let escrowed = self.escrowed();
+            if report.balances.checked_add(escrowed) != Some(self.supply) {
+                report.violations.push(InvariantViolation::BalanceMismatch {
+                    supply: self.supply,
+                    balances: report.balances,
+                    escrowed,
+                });
+            }
+        }
+
+        report
+    }
+}
+
+/// Balance commitment implementation.
+impl TokenState {
+    /// Mark the leaf of an account for update.
+    ///
+    /// The leaves are not updated right away, since the entry of an account is
+    /// modified after it is returned for modification, but once per call, when
+    /// the call returns or before the token-contract calls another contract.
+    /// See [`wrap_call`].
+    fn mark_modified(&mut self, key: AccountKey) {
+        self.modified_accounts.insert(key);
+    }
+
+    /// Bring the leaves of the modified accounts up to date, removing the
+    /// leaves of the accounts that were pruned. Each leaf is hashed once,
+    /// however many times the account was modified.
+    fn update_balances_tree(&mut self) {
+        for key in core::mem::take(&mut self.modified_accounts) {
+            let leaf =
+                self.accounts.get(&key).map(|(account, account_info)| {
+                    let preimage =
+                        merkle::leaf_preimage(*account, account_info);
+                    BalanceNode(abi::hash(preimage).to_bytes())
+                });
+
+            if let Some(leaf) = leaf {
+                let position = self.leaf_position(key);
+                self.balances_tree.insert(position, leaf);
+            } else if let Some(position) = self.leaf_positions.remove(&key) {
+                self.balances_tree.remove(position);
+                self.free_positions.push(position);
+            }
+        }
+    }
+
+    /// Return the position of the leaf of an account, assigning it a free
+    /// position if it has none yet.
+    fn leaf_position(&mut self, key: AccountKey) -> u64 {
+        if let Some(position) = self.leaf_positions.get(&key) {
+            return *position;
+        }
+
+        let assigned = self.leaf_positions.len() + self.free_positions.len();
+        let position = self.free_positions.pop().unwrap_or(assigned as u64);
+        self.leaf_positions.insert(key, position);
+
+        position
+    }
+
+    /// Return the root of the Merkle tree over the entries of the accounts.
+    fn balances_root(&self) -> [u8; 32] {
+        self.balances_tree.root().0
+    }
+
+    /// Return a proof of the entry of an account against the
+    /// [`balances_root`](Self::balances_root), or `None` if the account has
+    /// no entry.
+    fn balance_proof(&self, account: Account) -> Option<BalanceProof> {
+        let key = account_key(account);
+        let (_, account_info) = self.accounts.get(&key)?;
+        let position = *self.leaf_positions.get(&key)?;
+        let opening = self.balances_tree.opening(position)?;
+
+        // the opening holds the children of every level from the root down
+        let mut branch = [merkle::EMPTY_SUBTREE; merkle::HEIGHT];
+        let levels = opening.branch().iter().zip(opening.positions()).rev();
+        for (sibling, (children, index)) in branch.iter_mut().zip(levels) {
+            *sibling = children[1 - index].0;
+        }
+
+        Some(BalanceProof {
+            account_info: *account_info,
+            position,
+            branch,
+        })
+    }
+}
+
+/// Migration implementation.
+impl TokenState {
+    /// Export the state of up to `limit` accounts, in storage order starting
+    /// after the account `after`, or from the first account if `None`.
+    ///
+    /// # Note
+    /// holds, pending redemptions and pending transfers are not exported and
+    /// should be settled before the state is exported.
+    fn export_state(
+        &self,
+        after: Option<Account>,
+        limit: u32,
+    ) -> Vec<ExportedAccount> {
+        self.accounts_after(after)
+            .take(limit as usize)
+            .map(|(key, (account, account_info))| ExportedAccount {
+                account: *account,
+                balance: account_info.balance,
+                status: account_info.status,
+                sanction_reason: self
+                    .sanction_reasons
+                    .get(key)
+                    .map(|(_, reason)| *reason),
+                allowances: entries_of(&self.allowances, *key)
+                    .map(|(_, (spender, value))| (*spender, *value))
+                    .collect(),
+                operators: entries_of(&self.operators, *key)
+                    .map(|(_, operator)| *operator)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn import_finalized(&self) -> bool {
+        self.import_finalized
+    }
+
+    /// Import a batch of accounts exported by a previous token-contract.
+    ///
+    /// # Note
+    /// the state can only be imported while the contract is paused, before
+    /// the import is finalized.
+    fn import_state(&mut self, accounts: Vec<ExportedAccount>) {
+        assert_not_entered();
+        self.authorize_ownership();
+        assert!(!self.import_finalized, "{}", error::IMPORT_FINALIZED);
+        assert!(
+            self.is_paused,
+            "The state can only be imported while the contract is paused"
+        );
+
+        for exported in accounts {
+            let account = exported.account;
+            let keyed = KeyedAccount::new(account);
+            assert!(!self.reserved(keyed), "{}", error::RESERVED_ADDRESS);
+
+            // empty accounts are pruned after the import, so the imported
+            // accounts are recorded separately
+            assert!(
+                self.imported_accounts.insert(keyed.key),
+                "The account has already been imported"
+            );
+
+            // a sanctioned account always has a recorded reason, see
+            // `supply_breakdown`
+            assert!(
+                matches!(
+                    exported.status,
+                    AccountInfo::NO_STATUS
+                        | AccountInfo::FROZEN
+                        | AccountInfo::BLOCKED
+                ) && exported.sanction_reason.is_some()
+                    == (exported.status != AccountInfo::NO_STATUS),
+                "{}",
+                error::INVALID_IMPORTED_STATUS
+            );
+
+            let account_info = self.account_entry(keyed);
+            assert!(account_info.is_empty(), "The account already has state");
+
+            account_info.balance = exported.balance;
+            account_info.status = exported.status;
+
+            self.supply = if let Some(supply) =
+                self.supply.checked_add(exported.balance)
+            {
+                supply
+            } else {
+                panic!("{}", error::SUPPLY_OVERFLOW)
+            };
+            self.minted += u128::from(exported.balance);
+
+            if let Some(reason) = exported.sanction_reason {
+                self.sanction_reasons.insert(keyed.key, (account, reason));
+            }
+            for (spender, value) in exported.allowances {
+                let spender = KeyedAccount::new(spender);
+                *self.allowance_entry(keyed, spender) = value;
+            }
+            for operator in exported.operators {
+                self.operators
+                    .insert((keyed.key, account_key(operator)), operator);
+            }
+            self.prune_account(keyed);
+
+            self.emit_transfer_event(
+                events::Transfer::MINT_TOPIC,
+                events::Transfer {
+                    sender: ZERO_ADDRESS,
+                    spender: None,
+                    receiver: account,
+                    value: exported.balance,
+                    reason: None,
+                },
+            );
+        }
+    }
+
+    /// Finalize the import by checking the imported supply against the
+    /// `supply` exported by the previous token-contract. No more state can be
+    /// imported afterwards.
+    fn finalize_import(&mut self, supply: u64) {
+        self.authorize_ownership();
+        assert!(!self.import_finalized, "{}", error::IMPORT_FINALIZED);
+        assert!(
+            self.supply == supply,
+            "The imported supply doesn't match the exported supply"
+        );
+
+        self.import_finalized = true;
+
+        self.emit(
+            events::ImportFinalized::TOPIC,
+            events::ImportFinalized {
+                accounts: self.imported_accounts.len() as u64,
+                supply,
+            },
+        );
+    }
+}
+
+/// Signed call implementation.
+impl TokenState {
+    fn signed_call_nonce(&self, signer: AccountPublicKey) -> u64 {
+        self.signed_call_nonces
+            .get(&account_key(Account::External(signer)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Execute a call on behalf of the external account that signed it, e.g.
+    /// for a transaction paying its fees with shielded (Phoenix) notes which
+    /// has no public sender.
+    ///
+    /// The signature needs to be over the chain id and the signer's current
+    /// nonce, which is incremented with every executed call. Only the functions
+    /// holders use to move their own tokens can be executed this way.
+    fn signed_call(&mut self, call: &SignedCall) {
+        let signer = Account::External(call.signer);
+        let signer_key = account_key(signer);
+        let nonce = self
+            .signed_call_nonces
+            .get(&signer_key)
+            .copied()
+            .unwrap_or(0);
+
+        let sig_msg = SignedCall::signature_message(
+            abi::chain_id(),
+            &abi::self_id(),
+            self.currency,
+            nonce,
+            &call.fn_name,
+            &call.fn_args,
+        );
+        assert!(
+            abi::verify_bls(sig_msg, call.signer, call.signature),
+            "{}",
+            error::INVALID_SIGNATURE
+        );
+        self.signed_call_nonces.insert(signer_key, nonce + 1);
+
+        // the signer only replaces the sender of this call, not of the calls
+        // made back into the token-contract by the contracts it calls
+        let depth = abi::callstack().len();
+        // SAFETY: the contract is executed single-threaded and the signed
+        // sender is restored once the call returns
+        let previous =
+            unsafe { (*addr_of_mut!(SIGNED_SENDER)).replace((signer, depth)) };
+
+        match call.fn_name.as_str() {
+            "transfer" => {
+                let (receiver, value) = signed_call_args(&call.fn_args);
+                self.transfer(receiver, value, None);
+            }
+            "transfer_with_reference" => {
+                let (receiver, value, memo) = signed_call_args(&call.fn_args);
+                self.transfer(receiver, value, Some(memo));
+            }
+            "transfer_from" => {
+                let (owner, receiver, value) = signed_call_args(&call.fn_args);
+                self.transfer_from(owner, receiver, value, None);
+            }
+            "transfer_from_with_reference" => {
+                let (owner, receiver, value, memo) =
+                    signed_call_args(&call.fn_args);
+                self.transfer_from(owner, receiver, value, Some(memo));
+            }
+            "approve" => {
+                let (spender, value) = signed_call_args(&call.fn_args);
+                self.approve(spender, value);
+            }
+            "authorize_operator" => {
+                let operator = signed_call_args(&call.fn_args);
+                self.authorize_operator(operator);
+            }
+            "revoke_operator" => {
+                let operator = signed_call_args(&call.fn_args);
+                self.revoke_operator(operator);
+            }
+            "operator_send" => {
+                let (holder, receiver, value) = signed_call_args(&call.fn_args);
+                self.operator_send(holder, receiver, value);
+            }
+            _ => panic!("{}", error::SIGNED_CALL_NOT_SUPPORTED),
+        }
+
+        // SAFETY: see above
+        unsafe { SIGNED_SENDER = previous };
+    }
+}
+
+/// Operator implementation.
+impl TokenState {
+    fn is_operator_for(&self, operator: Account, holder: Account) -> bool {
+        self.is_operator(KeyedAccount::new(operator), KeyedAccount::new(holder))
+    }
+
+    fn is_operator(
+        &self,
+        operator: KeyedAccount,
+        holder: KeyedAccount,
+    ) -> bool {
+        self.operators.contains_key(&(holder.key, operator.key))
+    }
+
+    /// Authorize an operator to send the full balance of the sender.
+    fn authorize_operator(&mut self, operator: Account) {
+        assert_not_entered();
+        let holder = sender_account();
+        assert!(operator != holder, "The holder can't be its own operator");
+
+        // the holder of operators always has an entry, so that its operators
+        // are exported with it
+        let keyed = KeyedAccount::new(holder);
+        self.account_entry(keyed);
+        self.operators
+            .insert((keyed.key, account_key(operator)), operator);
+
+        self.emit(
+            events::OperatorUpdated::AUTHORIZED_TOPIC,
+            events::OperatorUpdated { holder, operator },
+        );
+    }
+
+    fn revoke_operator(&mut self, operator: Account) {
+        assert_not_entered();
+        let holder = sender_account();
+        let keyed = KeyedAccount::new(holder);
+
+        self.operators
+            .remove(&(keyed.key, account_key(operator)))
+            .expect(error::NOT_OPERATOR);
+        self.prune_account(keyed);
+
+        self.emit(
+            events::OperatorUpdated::REVOKED_TOPIC,
+            events::OperatorUpdated { holder, operator },
+        );
+    }
+
+    /// Send `value` tokens of the `holder` to the `receiver`, on behalf of the
+    /// holder. If the value exceeds the clearing threshold, the transfer is
+    /// queued until it is cleared.
+    ///
+    /// Note:
+    /// the operator must not be blocked or frozen.
+    /// the holder must not be blocked or frozen.
+    /// the receiver must not be blocked but can be frozen.
+    fn operator_send(
+        &mut self,
+        holder: Account,
+        receiver: Account,
+        value: u64,
+    ) {
+        assert_not_entered();
+        let operator = sender_account();
+        let keyed_holder = KeyedAccount::new(holder);
+        let keyed_receiver = KeyedAccount::new(receiver);
+
+        let status = self.operator_send_status(
+            KeyedAccount::new(operator),
+            keyed_holder,
+            keyed_receiver,
+            value,
+        );
+        if let Some(err) = status.error() {
+            panic!("{err}");
+        }
+
+        let holder_account = self
+            .account_info_mut(keyed_holder)
+            .expect(error::ACCOUNT_NOT_FOUND);
+        holder_account.balance -= value;
+
+        if status == TransferStatus::RequiresClearing {
+            self.queue_transfer(
+                holder,
+                Some(operator),
+                false,
+                keyed_receiver,
+                value,
+                None,
+            );
+            return;
+        }
+
+        let receiver_account = self.account_entry(keyed_receiver);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        receiver_account.balance += value;
+
+        self.emit_transfer_event(
+            events::Transfer::OPERATOR_SEND_TOPIC,
+            events::Transfer {
+                sender: holder,
+                spender: Some(operator),
+                receiver,
+                value,
+                reason: None,
+            },
+        );
+        self.notify_compliance(holder, receiver, value);
+    }
+}
+
+/// Preflight implementation.
+impl TokenState {
+    /// Run the checks of a transfer of `value` from `sender` to `receiver`,
+    /// without executing it.
+    fn can_transfer(
+        &self,
+        sender: Account,
+        receiver: Account,
+        value: u64,
+    ) -> TransferStatus {
+        self.transfer_status(
+            KeyedAccount::new(sender),
+            KeyedAccount::new(receiver),
+            value,
+        )
+    }
+
+    /// Run the checks of a transfer of `value` from `owner` to `receiver`,
+    /// executed by `spender`, without executing it.
+    #[allow(clippy::similar_names)]
+    fn can_transfer_from(
+        &self,
+        spender: Account,
+        owner: Account,
+        receiver: Account,
+        value: u64,
+    ) -> TransferStatus {
+        self.transfer_from_status(
+            KeyedAccount::new(spender),
+            KeyedAccount::new(owner),
+            KeyedAccount::new(receiver),
+            value,
+        )
+    }
+
+    /// Run the checks of a transfer of `value` from `holder` to `receiver`,
+    /// sent by the `operator`, without executing it.
+    fn can_operator_send(
+        &self,
+        operator: Account,
+        holder: Account,
+        receiver: Account,
+        value: u64,
+    ) -> TransferStatus {
+        self.operator_send_status(
+            KeyedAccount::new(operator),
+            KeyedAccount::new(holder),
+            KeyedAccount::new(receiver),
+            value,
+        )
+    }
+
+    /// The checks of [`can_transfer`](Self::can_transfer) on keyed accounts.
+    fn transfer_status(
+        &self,
+        sender: KeyedAccount,
+        receiver: KeyedAccount,
+        value: u64,
+    ) -> TransferStatus {
+        if self.is_paused {
+            return TransferStatus::Paused;
+        }
+        if self.reserved(receiver) {
+            return TransferStatus::ReservedAddress;
+        }
+
+        let status = self.send_status(sender, value);
+        if !status.is_allowed() {
+            return status;
+        }
+
+        self.compliance_status(sender, receiver, value)
+    }
+
+    /// The checks of [`can_transfer_from`](Self::can_transfer_from) on keyed
+    /// accounts.
+    #[allow(clippy::similar_names)]
+    fn transfer_from_status(
+        &self,
+        spender: KeyedAccount,
+        owner: KeyedAccount,
+        receiver: KeyedAccount,
+        value: u64,
+    ) -> TransferStatus {
+        if self.is_paused {
+            return TransferStatus::Paused;
+        }
+        if self.reserved(receiver) {
+            return TransferStatus::ReservedAddress;
+        }
+
+        let status = self.spender_status(spender);
+        if !status.is_allowed() {
+            return status;
+        }
+
+        let allowance = self.allowances.get(&(owner.key, spender.key));
+        let Some((_, allowance)) = allowance else {
+            return if self.has_allowances(owner) {
+                TransferStatus::SpenderNotAllowed
+            } else {
+                TransferStatus::NoAllowances
+            };
+        };
+        if value > *allowance {
+            return TransferStatus::AllowanceTooLow;
+        }
+
+        let status = self.send_status(owner, value);
+        if !status.is_allowed() {
+            return status;
+        }
+
+        self.compliance_status(owner, receiver, value)
+    }
+
+    /// The checks of [`can_operator_send`](Self::can_operator_send) on keyed
+    /// accounts.
+    fn operator_send_status(
+        &self,
+        operator: KeyedAccount,
+        holder: KeyedAccount,
+        receiver: KeyedAccount,
+        value: u64,
+    ) -> TransferStatus {
+        if self.is_paused {
+            return TransferStatus::Paused;
+        }
+        if self.reserved(receiver) {
+            return TransferStatus::ReservedAddress;
+        }
+
+        let status = self.spender_status(operator);
+        if !status.is_allowed() {
+            return status;
+        }
+
+        if !self.is_operator(operator, holder) {
+            return TransferStatus::NotOperator;
+        }
+
+        let status = self.send_status(holder, value);
+        if !status.is_allowed() {
+            return status;
+        }
+
+        self.compliance_status(holder, receiver, value)
+    }
+
+    /// Check whether the `spender` can spend the tokens of other accounts.
+    fn spender_status(&self, spender: KeyedAccount) -> TransferStatus {
+        let Some(spender_account) = self.account_info(spender) else {
+            return TransferStatus::Allowed;
+        };
+        if spender_account.is_blocked() {
+            return TransferStatus::SpenderBlocked;
+        }
+        if spender_account.is_frozen() {
+            return TransferStatus::SpenderFrozen;
+        }
+
+        TransferStatus::Allowed
+    }
+
+    /// Check whether `value` can be taken from the `sender` account.
+    fn send_status(&self, sender: KeyedAccount, value: u64) -> TransferStatus {
+        let Some(sender_account) = self.account_info(sender) else {
+            return TransferStatus::AccountNotFound;
+        };
+        if sender_account.is_blocked() {
+            return TransferStatus::SenderBlocked;
+        }
+        if sender_account.is_frozen() {
+            return TransferStatus::SenderFrozen;
+        }
+        if sender_account.spendable() < value {
+            return TransferStatus::BalanceTooLow;
+        }
+
+        TransferStatus::Allowed
+    }
+
+    /// Check the receiving side of a transfer, followed by the jurisdictions
+    /// of the accounts and the compliance contract. Whether the transfer
+    /// requires clearing is only checked once nothing vetoes it.
+    fn compliance_status(
+        &self,
+        sender: KeyedAccount,
+        receiver: KeyedAccount,
+        value: u64,
+    ) -> TransferStatus {
+        if self
+            .account_info(receiver)
+            .is_some_and(AccountInfo::is_blocked)
+        {
+            return TransferStatus::ReceiverBlocked;
+        }
+        if !self.jurisdictions_allowed(sender, receiver) {
+            return TransferStatus::JurisdictionRestricted;
+        }
+        if !self.complies(sender.account, receiver.account, value) {
+            return TransferStatus::ComplianceRejected;
+        }
+        if self.requires_clearing(value) {
+            return TransferStatus::RequiresClearing;
+        }
+
+        TransferStatus::Allowed
+    }
+}
+
+/// Administrative functions.
+impl TokenState {
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn toggle_pause(&mut self) {
+        self.authorize_ownership();
+
+        self.is_paused = !self.is_paused;
+
+        self.emit(
+            events::PauseToggled::TOPIC,
+            events::PauseToggled {
+                paused: self.is_paused,
+            },
+        );
+    }
+
+    /// Pause the contract. Can be called by the ownership and the guardians.
+    fn pause(&mut self) {
+        let sender = sender_account();
+        assert!(
+            sender == self.ownership
+                || self.guardians.contains_key(&account_key(sender)),
+            "{}",
+            error::UNAUTHORIZED_ACCOUNT
+        );
+        assert!(!self.is_paused, "{}", error::PAUSED_MESSAGE);
+
+        self.is_paused = true;
+
+        self.emit(
+            events::PauseToggled::TOPIC,
+            events::PauseToggled { paused: true },
+        );
+    }
+
+    /// Unpause the contract. Can only be called by the ownership.
+    fn unpause(&mut self) {
+        self.authorize_ownership();
+        assert!(self.is_paused, "{}", error::NOT_PAUSED);
+
+        self.is_paused = false;
+
+        self.emit(
+            events::PauseToggled::TOPIC,
+            events::PauseToggled { paused: false },
+        );
+    }
+
+    fn guardians(&self) -> Vec<Account> {
+        self.guardians.values().copied().collect()
+    }
+
+    fn is_guardian(&self, account: Account) -> bool {
+        self.guardians.contains_key(&account_key(account))
+    }
+
+    /// Add a guardian, allowed to pause the contract but not to unpause it.
+    fn add_guardian(&mut self, guardian: Account) {
+        self.authorize_ownership();
+
+        assert!(
+            self.guardians
+                .insert(account_key(guardian), guardian)
+                .is_none(),
+            "The account is already a guardian"
+        );
+
+        self.emit(
+            events::GuardianUpdated::ADDED_TOPIC,
+            events::GuardianUpdated { guardian },
+        );
+    }
+
+    fn remove_guardian(&mut self, guardian: Account) {
+        self.authorize_ownership();
+
+        assert!(
+            self.guardians.remove(&account_key(guardian)).is_some(),
+            "The account is not a guardian"
+        );
+
+        self.emit(
+            events::GuardianUpdated::REMOVED_TOPIC,
+            events::GuardianUpdated { guardian },
+        );
+    }
+
+    /// The compliance contract can't veto the transfer, but is notified of it.
+    ///
+    /// note: this function will fail if the balance of the obliged sender is
+    /// too low. It will **not** default to the maximum available balance.
+    fn force_transfer(
+        &mut self,
+        obliged_sender: Account,
+        receiver: Account,
+        value: u64,
+        reason: Reason,
+    ) {
+        assert_not_entered();
+        self.authorize_ownership();
+
+        let keyed_sender = KeyedAccount::new(obliged_sender);
+        let obliged_sender_account = self
+            .account_info_mut(keyed_sender)
+            .expect(error::ACCOUNT_NOT_FOUND);
+
+        assert!(
+            obliged_sender_account.spendable() >= value,
+            "{}",
+            error::BALANCE_TOO_LOW
+        );
+
+        obliged_sender_account.balance -= value;
+
+        let keyed_receiver = KeyedAccount::new(receiver);
+        assert!(
+            !self.reserved(keyed_receiver),
+            "{}",
+            error::RESERVED_ADDRESS
+        );
+
+        let receiver_account = self.account_entry(keyed_receiver);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        receiver_account.balance += value;
+
+        self.prune_account(keyed_sender);
+
+        self.emit_transfer_event(
+            events::Transfer::FORCE_TRANSFER_TOPIC,
+            events::Transfer {
+                sender: obliged_sender,
+                spender: None,
+                receiver,
+                value,
+                reason: Some(reason),
+            },
+        );
+        self.notify_compliance(obliged_sender, receiver, value);
+    }
+}
+
+/// Basic token-contract implementation.
+impl TokenState {
+    fn name(&self) -> String {
+        match &self.metadata {
+            Some(metadata) => metadata.name.clone(),
+            None => String::from("Electronic Money Token"),
+        }
+    }
+
+    fn symbol(&self) -> String {
+        match &self.metadata {
+            Some(metadata) => metadata.symbol.clone(),
+            None => String::from("EMT"),
+        }
+    }
+
+    fn decimals(&self) -> u8 {
+        match &self.metadata {
+            Some(metadata) => metadata.decimals,
+            None => 18,
+        }
+    }
+
+    fn total_supply(&self) -> u64 {
+        self.supply
+    }
+
+    fn account(&self, account: Account) -> AccountInfo {
+        self.account_info(KeyedAccount::new(account))
+            .copied()
+            .unwrap_or(AccountInfo::EMPTY)
+    }
+
+    fn balance_of(&self, account: Account) -> u64 {
+        match self.account_info(KeyedAccount::new(account)) {
+            Some(account_info) => account_info.balance,
+            None => 0,
+        }
+    }
+
+    #[allow(clippy::large_types_passed_by_value)]
+    fn allowance(&self, owner: Account, spender: Account) -> u64 {
+        match self
+            .allowances
+            .get(&(account_key(owner), account_key(spender)))
+        {
+            Some((_, allowance)) => *allowance,
+            None => 0,
+        }
+    }
+
+    /// Initates a `Transfer` from the sender to the receiver with the specified
+    /// value.
+    ///
+    /// Both the sender and the receiver are accounts.
+    ///
+    /// If the value exceeds the clearing threshold, the tokens are taken from
+    /// the sender and the transfer is queued until it is cleared.
+    ///
+    /// An optional `memo` of up to [`events::TransferReference::MAX_MEMO_SIZE`]
+    /// bytes is emitted with the transfer as a payment reference.
+    ///
+    /// # Note
+    /// the sender must not be blocked or frozen.
+    /// the receiver must not be blocked but can be frozen.
+    #[allow(clippy::large_types_passed_by_value)]
+    fn transfer(
+        &mut self,
+        receiver: Account,
+        value: u64,
+        memo: Option<Vec<u8>>,
+    ) {
+        assert_not_entered();
+        assert!(
+            memo.as_ref().map_or(0, Vec::len)
+                <= events::TransferReference::MAX_MEMO_SIZE,
+            "{}",
+            error::MEMO_TOO_LARGE
+        );
+
+        let sender = sender_account();
+        let keyed_sender = KeyedAccount::new(sender);
+        let keyed_receiver = KeyedAccount::new(receiver);
+
+        let status = self.transfer_status(keyed_sender, keyed_receiver, value);
+        if let Some(err) = status.error() {
+            panic!("{err}");
+        }
+
+        let sender_account = self
+            .account_info_mut(keyed_sender)
+            .expect(error::ACCOUNT_NOT_FOUND);
+        sender_account.balance -= value;
+
+        if status == TransferStatus::RequiresClearing {
+            self.queue_transfer(
+                sender,
+                None,
+                false,
+                keyed_receiver,
+                value,
+                memo,
+            );
+            self.prune_account(keyed_sender);
+            return;
+        }
+
+        let receiver_account = self.account_entry(keyed_receiver);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        receiver_account.balance += value;
+
+        self.prune_account(keyed_sender);
+
+        self.emit_transfer(sender, None, receiver, value, memo);
+        self.notify_compliance(sender, receiver, value);
     }
 
     /// Transfers tokens to a contract receiver and call a specified function on
@@ -450,200 +2850,672 @@ impl TokenState {
     /// - `transfer_and_call` is atomic: if the function call on the receiving
     ///   contract fails (due to a panic or out of gas error), the token
     ///   transfer also fails and reverts.
+    /// - Transfers exceeding the clearing threshold can not be queued and fail.
     fn transfer_and_call(&mut self, value: u64, contract_call: &ContractCall) {
+        assert_not_entered();
+        assert!(
+            !self.requires_clearing(value),
+            "{}",
+            error::CLEARING_REQUIRED
+        );
+
         let receiver = Account::from(contract_call.contract);
-        self.transfer(receiver, value);
+        self.transfer(receiver, value, None);
+        // the receiver may query the balances tree
+        self.update_balances_tree();
+
+        set_entered(true);
+        let result = abi::call_raw(
+            contract_call.contract,
+            &contract_call.fn_name,
+            &contract_call.fn_args,
+        );
+        set_entered(false);
+
+        // If the call to the contract fails (panic or OoG) the transfer
+        // also fails.
+        if let Err(err) = result {
+            panic!(
+                "Failed calling `{}` on the contract: {err}",
+                contract_call.fn_name
+            );
+        }
+    }
+
+    /// If the value exceeds the clearing threshold, the transfer is queued
+    /// until it is cleared.
+    ///
+    /// An optional `memo` is emitted with the transfer as a payment reference.
+    ///
+    /// Note:
+    /// the spender must not be blocked or frozen.
+    /// the actual owner of the funds must not be blocked or frozen.
+    /// the receiver must not be blocked but can be frozen.
+    #[allow(clippy::large_types_passed_by_value)]
+    fn transfer_from(
+        &mut self,
+        owner: Account,
+        receiver: Account,
+        value: u64,
+        memo: Option<Vec<u8>>,
+    ) {
+        assert_not_entered();
+        assert!(
+            memo.as_ref().map_or(0, Vec::len)
+                <= events::TransferReference::MAX_MEMO_SIZE,
+            "{}",
+            error::MEMO_TOO_LARGE
+        );
+
+        let spender = sender_account();
+        let keyed_spender = KeyedAccount::new(spender);
+        let keyed_owner = KeyedAccount::new(owner);
+        let keyed_receiver = KeyedAccount::new(receiver);
+
+        let status = self.transfer_from_status(
+            keyed_spender,
+            keyed_owner,
+            keyed_receiver,
+            value,
+        );
+        if let Some(err) = status.error() {
+            panic!("{err}");
+        }
+
+        let allowance = self
+            .allowance_mut(keyed_owner, keyed_spender)
+            .expect(error::SPENDER_NOT_ALLOWED);
+        *allowance -= value;
+
+        let owner_account = self
+            .account_info_mut(keyed_owner)
+            .expect(error::ACCOUNT_NOT_FOUND);
+        owner_account.balance -= value;
+
+        if status == TransferStatus::RequiresClearing {
+            self.queue_transfer(
+                owner,
+                Some(spender),
+                true,
+                keyed_receiver,
+                value,
+                memo,
+            );
+            self.prune_allowance(keyed_owner, keyed_spender);
+            self.prune_account(keyed_owner);
+            return;
+        }
+
+        let receiver_account = self.account_entry(keyed_receiver);
+
+        // this can never overflow as value + balance is never higher than total
+        // supply
+        receiver_account.balance += value;
+
+        self.prune_allowance(keyed_owner, keyed_spender);
+        self.prune_account(keyed_owner);
+
+        self.emit_transfer(owner, Some(spender), receiver, value, memo);
+        self.notify_compliance(owner, receiver, value);
+    }
+
+    fn approve(&mut self, spender: Account, value: u64) {
+        assert_not_entered();
+        // owner of the funds
+        let owner = sender_account();
+        let keyed_owner = KeyedAccount::new(owner);
+        let keyed_spender = KeyedAccount::new(spender);
+
+        // the owner of allowances always has an entry, so that its allowances
+        // are exported with it
+        self.account_entry(keyed_owner);
+
+        *self.allowance_entry(keyed_owner, keyed_spender) = value;
+
+        self.prune_allowance(keyed_owner, keyed_spender);
+        self.prune_account(keyed_owner);
+
+        self.emit(
+            events::Approve::APPROVE_TOPIC,
+            events::Approve {
+                sender: owner,
+                spender,
+                value,
+            },
+        );
+    }
+
+    /// Emit the event of a transfer between accounts, followed by the event
+    /// carrying its payment reference, if any.
+    #[allow(clippy::similar_names)]
+    fn emit_transfer(
+        &self,
+        sender: Account,
+        spender: Option<Account>,
+        receiver: Account,
+        value: u64,
+        memo: Option<Vec<u8>>,
+    ) {
+        self.emit_transfer_event(
+            events::Transfer::TRANSFER_TOPIC,
+            events::Transfer {
+                sender,
+                spender,
+                receiver,
+                value,
+                reason: None,
+            },
+        );
+
+        if let Some(memo) = memo {
+            self.emit(
+                events::TransferReference::TOPIC,
+                events::TransferReference {
+                    sender,
+                    spender,
+                    receiver,
+                    value,
+                    memo,
+                },
+            );
+        }
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn init(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(initial_accounts, ownership)| {
+        STATE.init(initial_accounts, ownership);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn name(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.name())
+}
+
+#[no_mangle]
+unsafe extern "C" fn symbol(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.symbol())
+}
+
+#[no_mangle]
+unsafe extern "C" fn decimals(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.decimals())
+}
+
+#[no_mangle]
+unsafe extern "C" fn total_supply(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.total_supply())
+}
+
+#[no_mangle]
+unsafe extern "C" fn event_sequence(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| *addr_of!(EVENT_SEQUENCE))
+}
+
+#[no_mangle]
+unsafe extern "C" fn account(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |arg| STATE.account(arg))
+}
+
+#[no_mangle]
+unsafe extern "C" fn balance_of(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.balance_of(account))
+}
+
+#[no_mangle]
+unsafe extern "C" fn allowance(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(owner, spender)| STATE.allowance(owner, spender))
+}
+
+#[no_mangle]
+unsafe extern "C" fn transfer(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(receiver, value)| {
+        STATE.transfer(receiver, value, None);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn transfer_with_reference(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(receiver, value, memo)| {
+        STATE.transfer(receiver, value, Some(memo));
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn transfer_and_call(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(transfer, contract_call)| {
+        STATE.transfer_and_call(transfer, &contract_call);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn transfer_from(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(owner, receiver, value)| {
+        STATE.transfer_from(owner, receiver, value, None);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn transfer_from_with_reference(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(owner, receiver, value, memo)| {
+        STATE.transfer_from(owner, receiver, value, Some(memo));
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn approve(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(spender, value)| STATE.approve(spender, value))
+}
+
+#[no_mangle]
+unsafe extern "C" fn authorize_operator(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |operator| STATE.authorize_operator(operator))
+}
+
+#[no_mangle]
+unsafe extern "C" fn revoke_operator(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |operator| STATE.revoke_operator(operator))
+}
+
+#[no_mangle]
+unsafe extern "C" fn is_operator_for(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(operator, holder)| {
+        STATE.is_operator_for(operator, holder)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn operator_send(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(holder, receiver, value)| {
+        STATE.operator_send(holder, receiver, value);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn can_transfer(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(sender, receiver, value)| {
+        STATE.can_transfer(sender, receiver, value)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn can_transfer_from(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(spender, owner, receiver, value)| {
+        STATE.can_transfer_from(spender, owner, receiver, value)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn can_operator_send(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(operator, holder, receiver, value)| {
+        STATE.can_operator_send(operator, holder, receiver, value)
+    })
+}
+
+/*
+ * Access control functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn transfer_ownership(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |new_ownership| {
+        STATE.transfer_ownership(new_ownership);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn renounce_ownership(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.renounce_ownership())
+}
+
+#[no_mangle]
+unsafe extern "C" fn ownership(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.ownership())
+}
+
+#[no_mangle]
+unsafe extern "C" fn add_reserved_address(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.add_reserved_address(account))
+}
+
+#[no_mangle]
+unsafe extern "C" fn is_reserved(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.is_reserved(account))
+}
+
+/*
+ * Supply management functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn minter(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.minter())
+}
+
+#[no_mangle]
+unsafe extern "C" fn set_minter(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |minter| STATE.set_minter(minter))
+}
+
+#[no_mangle]
+unsafe extern "C" fn renounce_minter(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.renounce_minter())
+}
+
+#[no_mangle]
+unsafe extern "C" fn mint(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(receiver, amount, reason)| {
+        STATE.mint(receiver, amount, reason);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn burn(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(amount, reason)| STATE.burn(amount, reason))
+}
+
+#[no_mangle]
+unsafe extern "C" fn controller_redeem(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(account, value, reason)| {
+        STATE.controller_redeem(account, value, reason);
+    })
+}
+
+/*
+ * Redemption functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn request_redemption(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(value, payout_reference)| {
+        STATE.request_redemption(value, payout_reference)
+    })
+}
 
-        // If the call to the contract fails (panic or OoG) the transfer
-        // also fails.
-        if let Err(err) = abi::call_raw(
-            contract_call.contract,
-            &contract_call.fn_name,
-            &contract_call.fn_args,
-        ) {
-            panic!(
-                "Failed calling `{}` on the contract: {err}",
-                contract_call.fn_name
-            );
-        }
-    }
+#[no_mangle]
+unsafe extern "C" fn settle_redemption(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(id, settlement_reference)| {
+        STATE.settle_redemption(id, settlement_reference);
+    })
+}
 
-    /// Note:
-    /// the spender must not be blocked or frozen.
-    /// the actual owner of the funds must not be blocked or frozen.
-    /// the receiver must not be blocked but can be frozen.
-    #[allow(clippy::large_types_passed_by_value)]
-    fn transfer_from(&mut self, owner: Account, receiver: Account, value: u64) {
-        assert!(!self.is_paused, "{}", error::PAUSED_MESSAGE);
+#[no_mangle]
+unsafe extern "C" fn reject_redemption(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(id, reason)| STATE.reject_redemption(id, reason))
+}
 
-        let spender = sender_account();
+#[no_mangle]
+unsafe extern "C" fn pending_redemptions(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.pending_redemptions())
+}
 
-        let spender_account =
-            self.accounts.entry(spender).or_insert(AccountInfo::EMPTY);
-        assert!(!spender_account.is_blocked(), "{}", error::BLOCKED);
-        assert!(!spender_account.is_frozen(), "{}", error::FROZEN);
+/*
+ * Hold functions
+ */
 
-        let allowance = self
-            .allowances
-            .get_mut(&owner)
-            .expect("The account has no allowances")
-            .get_mut(&spender)
-            .expect("The spender is not allowed to use the account");
+#[no_mangle]
+unsafe extern "C" fn hold(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(payee, notary, value, expiration)| {
+        STATE.hold(payee, notary, value, expiration)
+    })
+}
 
-        assert!(
-            value <= *allowance,
-            "The spender can't spent the defined amount"
-        );
+#[no_mangle]
+unsafe extern "C" fn hold_from(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(payer, payee, notary, value, expiration)| {
+        STATE.hold_from(payer, payee, notary, value, expiration)
+    })
+}
 
-        let owner_account = self
-            .accounts
-            .get_mut(&owner)
-            .expect(error::ACCOUNT_NOT_FOUND);
-        assert!(!owner_account.is_blocked(), "{}", error::BLOCKED);
-        assert!(!owner_account.is_frozen(), "{}", error::FROZEN);
+#[no_mangle]
+unsafe extern "C" fn execute_hold(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(id, value)| STATE.execute_hold(id, value))
+}
 
-        assert!(owner_account.balance >= value, "{}", error::BALANCE_TOO_LOW);
+#[no_mangle]
+unsafe extern "C" fn release_hold(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |id| STATE.release_hold(id))
+}
 
-        *allowance -= value;
-        owner_account.balance -= value;
+#[no_mangle]
+unsafe extern "C" fn hold_data(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |id| STATE.hold_data(id))
+}
 
-        let receiver_account =
-            self.accounts.entry(receiver).or_insert(AccountInfo::EMPTY);
-        assert!(!receiver_account.is_blocked(), "{}", error::BLOCKED);
+#[no_mangle]
+unsafe extern "C" fn balance_on_hold(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.balance_on_hold(account))
+}
 
-        // this can never overflow as value + balance is never higher than total
-        // supply
-        receiver_account.balance += value;
+/*
+ * Clearing functions
+ */
 
-        abi::emit(
-            events::Transfer::TRANSFER_TOPIC,
-            events::Transfer {
-                sender: owner,
-                spender: Some(spender),
-                receiver,
-                value,
-            },
-        );
-    }
+#[no_mangle]
+unsafe extern "C" fn set_clearing(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(threshold, agent)| {
+        STATE.set_clearing(threshold, agent);
+    })
+}
 
-    fn approve(&mut self, spender: Account, value: u64) {
-        // owner of the funds
-        let owner = sender_account();
+#[no_mangle]
+unsafe extern "C" fn clearing_threshold(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.clearing_threshold())
+}
 
-        let allowances = self.allowances.entry(owner).or_default();
+#[no_mangle]
+unsafe extern "C" fn clearing_agent(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.clearing_agent())
+}
 
-        allowances.insert(spender, value);
+#[no_mangle]
+unsafe extern "C" fn pending_transfers(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.pending_transfers())
+}
 
-        abi::emit(
-            events::Approve::APPROVE_TOPIC,
-            events::Approve {
-                sender: owner,
-                spender,
-                value,
-            },
-        );
-    }
+#[no_mangle]
+unsafe extern "C" fn approve_transfer(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |id| STATE.approve_transfer(id))
 }
 
 #[no_mangle]
-unsafe extern "C" fn init(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(initial_accounts, ownership)| {
-        STATE.init(initial_accounts, ownership);
+unsafe extern "C" fn reject_transfer(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(id, reason)| STATE.reject_transfer(id, reason))
+}
+
+#[no_mangle]
+unsafe extern "C" fn cancel_transfer(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |id| STATE.cancel_transfer(id))
+}
+
+/*
+ * Compliance functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn set_compliance(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |compliance| STATE.set_compliance(compliance))
+}
+
+#[no_mangle]
+unsafe extern "C" fn compliance(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.compliance())
+}
+
+/*
+ * Reserve attestation functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn attestor(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.attestor())
+}
+
+#[no_mangle]
+unsafe extern "C" fn attestation_validity(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.attestation_validity())
+}
+
+#[no_mangle]
+unsafe extern "C" fn reserve_attestation(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.reserve_attestation())
+}
+
+#[no_mangle]
+unsafe extern "C" fn set_attestor(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(attestor, validity)| {
+        STATE.set_attestor(attestor, validity);
     })
 }
 
 #[no_mangle]
-unsafe extern "C" fn name(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| TokenState::name())
+unsafe extern "C" fn attest_reserves(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(reserves, timestamp, report_hash)| {
+        STATE.attest_reserves(reserves, timestamp, report_hash);
+    })
 }
 
+/*
+ * Document functions
+ */
+
 #[no_mangle]
-unsafe extern "C" fn symbol(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| TokenState::symbol())
+unsafe extern "C" fn document(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |name: String| STATE.document(&name))
 }
 
 #[no_mangle]
-unsafe extern "C" fn decimals(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| TokenState::decimals())
+unsafe extern "C" fn documents(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.documents())
 }
 
 #[no_mangle]
-unsafe extern "C" fn total_supply(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| STATE.total_supply())
+unsafe extern "C" fn set_document(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(name, uri, hash)| {
+        STATE.set_document(name, uri, hash);
+    })
 }
 
 #[no_mangle]
-unsafe extern "C" fn account(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |arg| STATE.account(arg))
+unsafe extern "C" fn remove_document(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |name| STATE.remove_document(name))
+}
+
+/*
+ * Attribute functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn attributes(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.attributes(account))
 }
 
 #[no_mangle]
-unsafe extern "C" fn balance_of(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |account| STATE.balance_of(account))
+unsafe extern "C" fn set_attributes(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(account, attributes)| {
+        STATE.set_attributes(account, attributes);
+    })
 }
 
 #[no_mangle]
-unsafe extern "C" fn allowance(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(owner, spender)| STATE.allowance(owner, spender))
+unsafe extern "C" fn attribute_manager(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.attribute_manager())
 }
 
 #[no_mangle]
-unsafe extern "C" fn transfer(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(receiver, value)| STATE.transfer(receiver, value))
+unsafe extern "C" fn set_attribute_manager(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |manager| STATE.set_attribute_manager(manager))
 }
 
 #[no_mangle]
-unsafe extern "C" fn transfer_and_call(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(transfer, contract_call)| {
-        STATE.transfer_and_call(transfer, &contract_call);
+unsafe extern "C" fn jurisdictions_restricted(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(sender, receiver)| {
+        STATE.jurisdictions_restricted(sender, receiver)
     })
 }
 
 #[no_mangle]
-unsafe extern "C" fn transfer_from(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(owner, receiver, value)| {
-        STATE.transfer_from(owner, receiver, value);
+unsafe extern "C" fn restrict_jurisdictions(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(sender, receiver)| {
+        STATE.restrict_jurisdictions(sender, receiver);
     })
 }
 
 #[no_mangle]
-unsafe extern "C" fn approve(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(spender, value)| STATE.approve(spender, value))
+unsafe extern "C" fn unrestrict_jurisdictions(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(sender, receiver)| {
+        STATE.unrestrict_jurisdictions(sender, receiver);
+    })
 }
 
 /*
- * Access control functions
+ * Balance commitment functions
  */
 
 #[no_mangle]
-unsafe extern "C" fn transfer_ownership(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |new_ownership| {
-        STATE.transfer_ownership(new_ownership);
+unsafe extern "C" fn balances_root(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.balances_root())
+}
+
+#[no_mangle]
+unsafe extern "C" fn balance_proof(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.balance_proof(account))
+}
+
+/*
+ * Audit functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn supply_breakdown(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.supply_breakdown())
+}
+
+#[no_mangle]
+unsafe extern "C" fn verify_invariants(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(after, balances, limit)| {
+        STATE.verify_invariants(after, balances, limit)
     })
 }
 
+/*
+ * Migration functions
+ */
+
 #[no_mangle]
-unsafe extern "C" fn renounce_ownership(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| STATE.renounce_ownership())
+unsafe extern "C" fn export_state(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(after, limit)| STATE.export_state(after, limit))
 }
 
 #[no_mangle]
-unsafe extern "C" fn ownership(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| STATE.ownership())
+unsafe extern "C" fn import_state(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |accounts| STATE.import_state(accounts))
+}
+
+#[no_mangle]
+unsafe extern "C" fn finalize_import(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |supply| STATE.finalize_import(supply))
+}
+
+#[no_mangle]
+unsafe extern "C" fn import_finalized(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.import_finalized())
 }
 
 /*
- * Supply management functions
+ * Signed call functions
  */
 
 #[no_mangle]
-unsafe extern "C" fn mint(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(receiver, amount)| STATE.mint(receiver, amount))
+unsafe extern "C" fn signed_call(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |call| STATE.signed_call(&call))
 }
 
 #[no_mangle]
-unsafe extern "C" fn burn(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |arg| STATE.burn(arg))
+unsafe extern "C" fn signed_call_nonce(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |signer| STATE.signed_call_nonce(signer))
 }
 
 /*
@@ -652,18 +3524,55 @@ unsafe extern "C" fn burn(arg_len: u32) -> u32 {
 
 #[no_mangle]
 unsafe extern "C" fn toggle_pause(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| STATE.toggle_pause())
+    wrap_call(arg_len, |(): ()| STATE.toggle_pause())
 }
 
 #[no_mangle]
 unsafe extern "C" fn is_paused(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(): ()| STATE.is_paused())
+    wrap_call(arg_len, |(): ()| STATE.is_paused())
+}
+
+#[no_mangle]
+unsafe extern "C" fn pause(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.pause())
+}
+
+#[no_mangle]
+unsafe extern "C" fn unpause(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.unpause())
+}
+
+#[no_mangle]
+unsafe extern "C" fn guardians(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.guardians())
+}
+
+#[no_mangle]
+unsafe extern "C" fn is_guardian(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |account| STATE.is_guardian(account))
+}
+
+#[no_mangle]
+unsafe extern "C" fn add_guardian(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |guardian| STATE.add_guardian(guardian))
+}
+
+#[no_mangle]
+unsafe extern "C" fn remove_guardian(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |guardian| STATE.remove_guardian(guardian))
+}
+
+#[no_mangle]
+unsafe extern "C" fn compact_accounts(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(after, limit)| {
+        STATE.compact_accounts(after, limit)
+    })
 }
 
 #[no_mangle]
 unsafe extern "C" fn force_transfer(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |(obliged_sender, receiver, value)| {
-        STATE.force_transfer(obliged_sender, receiver, value);
+    wrap_call(arg_len, |(obliged_sender, receiver, value, reason)| {
+        STATE.force_transfer(obliged_sender, receiver, value, reason);
     })
 }
 
@@ -673,40 +3582,521 @@ unsafe extern "C" fn force_transfer(arg_len: u32) -> u32 {
 
 #[no_mangle]
 unsafe extern "C" fn block(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |acc| STATE.block(acc))
+    wrap_call(arg_len, |(acc, reason)| STATE.block(acc, reason))
 }
 
 #[no_mangle]
 unsafe extern "C" fn freeze(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |acc| STATE.freeze(acc))
+    wrap_call(arg_len, |(acc, reason)| STATE.freeze(acc, reason))
 }
 
 #[no_mangle]
 unsafe extern "C" fn unblock(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |acc| STATE.unblock(acc))
+    wrap_call(arg_len, |(acc, reason)| STATE.unblock(acc, reason))
 }
 
 #[no_mangle]
 unsafe extern "C" fn unfreeze(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |acc| STATE.unfreeze(acc))
+    wrap_call(arg_len, |(acc, reason)| STATE.unfreeze(acc, reason))
 }
 
 #[no_mangle]
 unsafe extern "C" fn blocked(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |acc| STATE.blocked(acc))
+    wrap_call(arg_len, |acc| STATE.blocked(acc))
 }
 
 #[no_mangle]
 unsafe extern "C" fn frozen(arg_len: u32) -> u32 {
-    abi::wrap_call(arg_len, |arg| STATE.frozen(arg))
+    wrap_call(arg_len, |arg| STATE.frozen(arg))
+}
+
+#[no_mangle]
+unsafe extern "C" fn sanction_reason(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |acc| STATE.sanction_reason(acc))
+}
+
+/*
+ * Multi-currency functions
+ */
+
+#[no_mangle]
+unsafe extern "C" fn create_currency(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(currency, metadata, ownership)| {
+        STATE.create_currency(currency, metadata, ownership);
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn currencies(arg_len: u32) -> u32 {
+    wrap_call(arg_len, |(): ()| STATE.currencies())
+}
+
+/// The state of the given currency.
+///
+/// # Panics
+/// If the currency does not exist.
+unsafe fn ledger(currency: CurrencyId) -> &'static mut TokenState {
+    let state = &mut *addr_of_mut!(STATE);
+    if currency == DEFAULT_CURRENCY {
+        state
+    } else {
+        state
+            .currencies
+            .get_mut(&currency)
+            .expect(error::CURRENCY_NOT_FOUND)
+    }
+}
+
+/// Define an entrypoint taking the currency id as first argument, followed by
+/// the arguments of the corresponding entrypoint of the default currency.
+macro_rules! currency_entrypoint {
+    ($name:ident, |$state:ident, $args:pat_param| $call:expr) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(arg_len: u32) -> u32 {
+            wrap_call(arg_len, |(currency, $args): (CurrencyId, _)| {
+                let $state = ledger(currency);
+                $call
+            })
+        }
+    };
 }
 
+currency_entrypoint!(currency_name, |state, ()| state.name());
+currency_entrypoint!(currency_symbol, |state, ()| state.symbol());
+currency_entrypoint!(currency_decimals, |state, ()| state.decimals());
+currency_entrypoint!(currency_total_supply, |state, ()| state.total_supply());
+currency_entrypoint!(currency_account, |state, account| state.account(account));
+currency_entrypoint!(currency_balance_of, |state, account| {
+    state.balance_of(account)
+});
+currency_entrypoint!(currency_allowance, |state, (owner, spender)| {
+    state.allowance(owner, spender)
+});
+currency_entrypoint!(currency_transfer, |state, (receiver, value)| {
+    state.transfer(receiver, value, None);
+});
+currency_entrypoint!(currency_transfer_with_reference, |state,
+                                                        (
+    receiver,
+    value,
+    memo,
+)| {
+    state.transfer(receiver, value, Some(memo));
+});
+currency_entrypoint!(
+    currency_transfer_and_call,
+    |state, (value, contract_call)| {
+        state.transfer_and_call(value, &contract_call);
+    }
+);
+currency_entrypoint!(
+    currency_transfer_from,
+    |state, (owner, receiver, value)| {
+        state.transfer_from(owner, receiver, value, None);
+    }
+);
+currency_entrypoint!(currency_transfer_from_with_reference, |state,
+                                                             (
+    owner,
+    receiver,
+    value,
+    memo,
+)| {
+    state.transfer_from(owner, receiver, value, Some(memo));
+});
+currency_entrypoint!(currency_approve, |state, (spender, value)| {
+    state.approve(spender, value);
+});
+currency_entrypoint!(
+    currency_can_transfer,
+    |state, (sender, receiver, value)| {
+        state.can_transfer(sender, receiver, value)
+    }
+);
+currency_entrypoint!(currency_can_transfer_from, |state,
+                                                  (
+    spender,
+    owner,
+    receiver,
+    value,
+)| {
+    state.can_transfer_from(spender, owner, receiver, value)
+});
+currency_entrypoint!(currency_can_operator_send, |state,
+                                                  (
+    operator,
+    holder,
+    receiver,
+    value,
+)| {
+    state.can_operator_send(operator, holder, receiver, value)
+});
+currency_entrypoint!(currency_authorize_operator, |state, operator| {
+    state.authorize_operator(operator);
+});
+currency_entrypoint!(currency_revoke_operator, |state, operator| {
+    state.revoke_operator(operator);
+});
+currency_entrypoint!(currency_is_operator_for, |state, (operator, holder)| {
+    state.is_operator_for(operator, holder)
+});
+currency_entrypoint!(
+    currency_operator_send,
+    |state, (holder, receiver, value)| {
+        state.operator_send(holder, receiver, value);
+    }
+);
+currency_entrypoint!(currency_transfer_ownership, |state, new_ownership| {
+    state.transfer_ownership(new_ownership);
+});
+currency_entrypoint!(currency_renounce_ownership, |state, ()| {
+    state.renounce_ownership();
+});
+currency_entrypoint!(currency_ownership, |state, ()| state.ownership());
+currency_entrypoint!(currency_add_reserved_address, |state, account| {
+    state.add_reserved_address(account);
+});
+currency_entrypoint!(currency_is_reserved, |state, account| {
+    state.is_reserved(account)
+});
+currency_entrypoint!(currency_minter, |state, ()| state.minter());
+currency_entrypoint!(currency_set_minter, |state, minter| {
+    state.set_minter(minter);
+});
+currency_entrypoint!(currency_renounce_minter, |state, ()| {
+    state.renounce_minter();
+});
+currency_entrypoint!(currency_mint, |state, (receiver, amount, reason)| {
+    state.mint(receiver, amount, reason);
+});
+currency_entrypoint!(currency_burn, |state, (amount, reason)| {
+    state.burn(amount, reason);
+});
+currency_entrypoint!(
+    currency_controller_redeem,
+    |state, (account, value, reason)| {
+        state.controller_redeem(account, value, reason);
+    }
+);
+currency_entrypoint!(currency_request_redemption, |state,
+                                                   (
+    value,
+    payout_reference,
+)| {
+    state.request_redemption(value, payout_reference)
+});
+currency_entrypoint!(currency_settle_redemption, |state,
+                                                  (
+    id,
+    settlement_reference,
+)| {
+    state.settle_redemption(id, settlement_reference);
+});
+currency_entrypoint!(currency_reject_redemption, |state, (id, reason)| {
+    state.reject_redemption(id, reason);
+});
+currency_entrypoint!(currency_pending_redemptions, |state, ()| {
+    state.pending_redemptions()
+});
+currency_entrypoint!(
+    currency_hold,
+    |state, (payee, notary, value, expiration)| {
+        state.hold(payee, notary, value, expiration)
+    }
+);
+currency_entrypoint!(currency_hold_from, |state,
+                                          (
+    payer,
+    payee,
+    notary,
+    value,
+    expiration,
+)| {
+    state.hold_from(payer, payee, notary, value, expiration)
+});
+currency_entrypoint!(currency_execute_hold, |state, (id, value)| {
+    state.execute_hold(id, value);
+});
+currency_entrypoint!(currency_release_hold, |state, id| {
+    state.release_hold(id);
+});
+currency_entrypoint!(currency_hold_data, |state, id| state.hold_data(id));
+currency_entrypoint!(currency_balance_on_hold, |state, account| {
+    state.balance_on_hold(account)
+});
+currency_entrypoint!(currency_set_clearing, |state, (threshold, agent)| {
+    state.set_clearing(threshold, agent);
+});
+currency_entrypoint!(currency_clearing_threshold, |state, ()| {
+    state.clearing_threshold()
+});
+currency_entrypoint!(currency_clearing_agent, |state, ()| {
+    state.clearing_agent()
+});
+currency_entrypoint!(currency_pending_transfers, |state, ()| {
+    state.pending_transfers()
+});
+currency_entrypoint!(currency_approve_transfer, |state, id| {
+    state.approve_transfer(id);
+});
+currency_entrypoint!(currency_reject_transfer, |state, (id, reason)| {
+    state.reject_transfer(id, reason);
+});
+currency_entrypoint!(currency_cancel_transfer, |state, id| {
+    state.cancel_transfer(id);
+});
+currency_entrypoint!(currency_set_compliance, |state, compliance| {
+    state.set_compliance(compliance);
+});
+currency_entrypoint!(currency_compliance, |state, ()| state.compliance());
+currency_entrypoint!(currency_attestor, |state, ()| state.attestor());
+currency_entrypoint!(currency_attestation_validity, |state, ()| {
+    state.attestation_validity()
+});
+currency_entrypoint!(currency_reserve_attestation, |state, ()| {
+    state.reserve_attestation()
+});
+currency_entrypoint!(currency_set_attestor, |state, (attestor, validity)| {
+    state.set_attestor(attestor, validity);
+});
+currency_entrypoint!(currency_attest_reserves, |state,
+                                                (
+    reserves,
+    timestamp,
+    report_hash,
+)| {
+    state.attest_reserves(reserves, timestamp, report_hash);
+});
+currency_entrypoint!(currency_document, |state, name| {
+    let name: String = name;
+    state.document(&name)
+});
+currency_entrypoint!(currency_documents, |state, ()| state.documents());
+currency_entrypoint!(currency_set_document, |state, (name, uri, hash)| {
+    state.set_document(name, uri, hash);
+});
+currency_entrypoint!(currency_remove_document, |state, name| {
+    state.remove_document(name);
+});
+currency_entrypoint!(currency_attributes, |state, account| {
+    state.attributes(account)
+});
+currency_entrypoint!(
+    currency_set_attributes,
+    |state, (account, attributes)| {
+        state.set_attributes(account, attributes);
+    }
+);
+currency_entrypoint!(currency_attribute_manager, |state, ()| {
+    state.attribute_manager()
+});
+currency_entrypoint!(currency_set_attribute_manager, |state, manager| {
+    state.set_attribute_manager(manager);
+});
+currency_entrypoint!(currency_jurisdictions_restricted, |state,
+                                                         (
+    sender,
+    receiver,
+)| {
+    state.jurisdictions_restricted(sender, receiver)
+});
+currency_entrypoint!(
+    currency_restrict_jurisdictions,
+    |state, (sender, receiver)| {
+        state.restrict_jurisdictions(sender, receiver);
+    }
+);
+currency_entrypoint!(currency_unrestrict_jurisdictions, |state,
+                                                         (
+    sender,
+    receiver,
+)| {
+    state.unrestrict_jurisdictions(sender, receiver);
+});
+currency_entrypoint!(currency_balances_root, |state, ()| {
+    state.balances_root()
+});
+currency_entrypoint!(currency_balance_proof, |state, account| {
+    state.balance_proof(account)
+});
+currency_entrypoint!(currency_supply_breakdown, |state, ()| {
+    state.supply_breakdown()
+});
+currency_entrypoint!(
+    currency_verify_invariants,
+    |state, (after, balances, limit)| {
+        state.verify_invariants(after, balances, limit)
+    }
+);
+currency_entrypoint!(currency_export_state, |state, (after, limit)| {
+    state.export_state(after, limit)
+});
+currency_entrypoint!(currency_import_state, |state, accounts| {
+    state.import_state(accounts);
+});
+currency_entrypoint!(currency_finalize_import, |state, supply| {
+    state.finalize_import(supply);
+});
+currency_entrypoint!(currency_import_finalized, |state, ()| {
+    state.import_finalized()
+});
+currency_entrypoint!(currency_signed_call, |state, call| {
+    state.signed_call(&call);
+});
+currency_entrypoint!(currency_signed_call_nonce, |state, signer| {
+    state.signed_call_nonce(signer)
+});
+currency_entrypoint!(currency_toggle_pause, |state, ()| {
+    state.toggle_pause();
+});
+currency_entrypoint!(currency_is_paused, |state, ()| state.is_paused());
+currency_entrypoint!(currency_pause, |state, ()| state.pause());
+currency_entrypoint!(currency_unpause, |state, ()| state.unpause());
+currency_entrypoint!(currency_guardians, |state, ()| state.guardians());
+currency_entrypoint!(currency_is_guardian, |state, account| {
+    state.is_guardian(account)
+});
+currency_entrypoint!(currency_add_guardian, |state, guardian| {
+    state.add_guardian(guardian);
+});
+currency_entrypoint!(currency_remove_guardian, |state, guardian| {
+    state.remove_guardian(guardian);
+});
+currency_entrypoint!(currency_compact_accounts, |state, (after, limit)| {
+    state.compact_accounts(after, limit)
+});
+currency_entrypoint!(currency_force_transfer, |state,
+                                               (
+    obliged_sender,
+    receiver,
+    value,
+    reason,
+)| {
+    state.force_transfer(obliged_sender, receiver, value, reason);
+});
+currency_entrypoint!(currency_block, |state, (account, reason)| {
+    state.block(account, reason);
+});
+currency_entrypoint!(currency_freeze, |state, (account, reason)| {
+    state.freeze(account, reason);
+});
+currency_entrypoint!(currency_unblock, |state, (account, reason)| {
+    state.unblock(account, reason);
+});
+currency_entrypoint!(currency_unfreeze, |state, (account, reason)| {
+    state.unfreeze(account, reason);
+});
+currency_entrypoint!(currency_blocked, |state, account| state.blocked(account));
+currency_entrypoint!(currency_frozen, |state, account| state.frozen(account));
+currency_entrypoint!(currency_sanction_reason, |state, account| {
+    state.sanction_reason(account)
+});
+
 /*
  * Helper functions
  */
 
+/// Wrap a call to the token-contract like [`abi::wrap_call`], bringing the
+/// balances trees of all currencies up to date once the call returns.
+///
+/// Modifying an account only marks its leaf for update, so that the leaves
+/// are hashed once per call rather than on every modification.
+fn wrap_call<A, R, F>(arg_len: u32, f: F) -> u32
+where
+    A: Archive,
+    A::Archived:
+        Deserialize<A, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
+    R: for<'b> Serialize<StandardBufSerializer<'b>>,
+    F: Fn(A) -> R,
+{
+    abi::wrap_call(arg_len, |arg| {
+        let result = f(arg);
+
+        // SAFETY: the contract is executed single-threaded and the call has
+        // returned
+        let state = unsafe { &mut *addr_of_mut!(STATE) };
+        state.update_balances_tree();
+        for currency in state.currencies.values_mut() {
+            currency.update_balances_tree();
+        }
+
+        result
+    })
+}
+
+/// Compute the key under which an account is stored, hashing its raw
+/// encoding on the host.
+fn account_key(account: Account) -> AccountKey {
+    let mut bytes = Vec::new();
+    match account {
+        Account::External(pk) => {
+            bytes.push(0);
+            bytes.extend_from_slice(&pk.to_raw_bytes());
+        }
+        Account::Contract(contract) => {
+            bytes.push(1);
+            bytes.extend_from_slice(contract.as_bytes());
+        }
+    }
+    abi::hash(bytes).to_bytes()
+}
+
+/// The entries of a map keyed by pairs of accounts whose first account has the
+/// given key.
+fn entries_of<V>(
+    map: &BTreeMap<(AccountKey, AccountKey), V>,
+    key: AccountKey,
+) -> btree_map::Range<'_, (AccountKey, AccountKey), V> {
+    map.range((key, [0; 32])..=(key, [u8::MAX; 32]))
+}
+
+/// Return the sequence number of the next event emitted.
+fn next_event_sequence() -> u64 {
+    unsafe {
+        EVENT_SEQUENCE += 1;
+        EVENT_SEQUENCE
+    }
+}
+
+/// Panic if the receiver of a `transfer_and_call` is being called.
+///
+/// # Policy
+///
+/// While the receiver of a `transfer_and_call` is called, it may re-enter the
+/// token-contract to query its state, but not to modify balances, the supply,
+/// allowances or operators. The following functions call this guard first and
+/// panic when re-entered, which reverts the outer call only if the receiver
+/// doesn't handle the error:
+///
+/// - `transfer`, `transfer_from`, `transfer_and_call` and `approve`
+/// - `authorize_operator`, `revoke_operator` and `operator_send`
+/// - `mint`, `burn`, `controller_redeem` and `force_transfer`
+/// - `request_redemption`, `settle_redemption` and `reject_redemption`
+/// - `hold`, `hold_from`, `execute_hold` and `release_hold`
+/// - `approve_transfer`, `reject_transfer` and `cancel_transfer`
+/// - `import_state`
+///
+/// The lock is held across the whole contract, so that the receiver can't
+/// re-enter it in any currency either.
+fn assert_not_entered() {
+    // SAFETY: the contract is executed single-threaded
+    let entered = unsafe { core::ptr::read_volatile(addr_of!(ENTERED)) };
+    assert!(!entered, "{}", error::REENTRANT_CALL);
+}
+
+/// Set the lock checked by [`assert_not_entered`].
+///
+/// The lock is accessed volatile, since it is only read again through the
+/// re-entrant calls the compiler doesn't see.
+fn set_entered(entered: bool) {
+    // SAFETY: the contract is executed single-threaded
+    unsafe { core::ptr::write_volatile(addr_of_mut!(ENTERED), entered) };
+}
+
 /// Determines and returns the sender of the current transfer.
 ///
+/// If a signed call is executed and the token-contract is not re-entered from
+/// it, return its signer.
 /// If the sender is an external account, return the transaction origin.
 /// If the sender is a contract, return the calling contract.
 ///
@@ -716,9 +4106,17 @@ unsafe extern "C" fn frozen(arg_len: u32) -> u32 {
 ///
 /// # Panics
 ///
-/// - If no public sender is available (shielded transactions are not supported)
+/// - If no public sender is available (shielded transactions are only supported
+///   through signed calls)
 /// - If no caller can be determined (impossible case)
 fn sender_account() -> Account {
+    // SAFETY: the contract is executed single-threaded
+    if let Some((signer, depth)) = unsafe { *addr_of!(SIGNED_SENDER) } {
+        if abi::callstack().len() == depth {
+            return signer;
+        }
+    }
+
     // Identifies the sender by checking the call stack and transaction origin:
     // - For direct external account transactions (call stack length = 1),
     //   returns the transaction origin
@@ -740,3 +4138,25 @@ fn sender_account() -> Account {
         Account::Contract(abi::caller().expect("ICC expects a caller"))
     }
 }
+
+/// Deserializes the arguments of a signed call.
+///
+/// # Panics
+///
+/// - If the arguments don't match the called function
+fn signed_call_args<A>(fn_args: &[u8]) -> A
+where
+    A: Archive,
+    A::Archived:
+        Deserialize<A, Infallible> + for<'a> CheckBytes<DefaultValidator<'a>>,
+{
+    // the archived arguments need to be aligned
+    let mut aligned = AlignedVec::with_capacity(fn_args.len());
+    aligned.extend_from_slice(fn_args);
+
+    let archived = rkyv::check_archived_root::<A>(&aligned)
+        .unwrap_or_else(|_| panic!("{}", error::INVALID_SIGNED_CALL_ARGUMENTS));
+    archived
+        .deserialize(&mut Infallible)
+        .expect("Infallible deserialization")
+}
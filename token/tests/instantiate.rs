@@ -12,6 +12,9 @@ use dusk_core::dusk;
 use dusk_core::signatures::bls::{
     PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
 };
+use dusk_core::transfer::phoenix::{
+    Note, PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey,
+};
 use dusk_vm::{CallReceipt, ContractData, Error as VMError};
 
 use bytecheck::CheckBytes;
@@ -32,19 +35,48 @@ const TOKEN_BYTECODE: &[u8] = include_bytes!(
 const HOLDER_BYTECODE: &[u8] = include_bytes!(
     "../../target/wasm64-unknown-unknown/release/emt_holder_contract.wasm"
 );
+const COMPLIANCE_BYTECODE: &[u8] = include_bytes!(
+    "../../target/wasm64-unknown-unknown/release/emt_compliance_contract.wasm"
+);
+const MALICIOUS_BYTECODE: &[u8] = include_bytes!(
+    "../../target/wasm64-unknown-unknown/release/emt_malicious_contract.wasm"
+);
 
 const DEPLOYER: [u8; 64] = [0u8; 64];
 
 pub const TOKEN_ID: ContractId = ContractId::from_bytes([1; 32]);
 pub const HOLDER_ID: ContractId = ContractId::from_bytes([2; 32]);
+pub const COMPLIANCE_ID: ContractId = ContractId::from_bytes([4; 32]);
+pub const MALICIOUS_ID: ContractId = ContractId::from_bytes([5; 32]);
 
-pub const MOONLIGHT_BALANCE: u64 = dusk(1_000.0);
+/// Enough to pay for the transactions filling the state in the gas benchmark.
+pub const MOONLIGHT_BALANCE: u64 = dusk(1_000_000.0);
 pub const INITIAL_BALANCE: u64 = 1000;
 pub const INITIAL_HOLDER_BALANCE: u64 = 1000;
 pub const INITIAL_OWNERSHIP_BALANCE: u64 = 1000;
 pub const INITIAL_SUPPLY: u64 =
     INITIAL_BALANCE + INITIAL_HOLDER_BALANCE + INITIAL_OWNERSHIP_BALANCE;
 
+/// The reason given by the ownership for administrative actions in the tests.
+pub const TEST_REASON: Reason = Reason::new(1, [42; 32]);
+
+/// Deserialize the data of an event emitted by the token-contract, dropping
+/// its sequence number.
+pub fn event_data<E>(data: &[u8]) -> E
+where
+    E: Archive,
+    E::Archived:
+        Deserialize<E, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
+{
+    let event =
+        rkyv::check_archived_root::<token::events::SequencedEvent<E>>(data)
+            .expect("the event data should be valid");
+    event
+        .event
+        .deserialize(&mut Infallible)
+        .expect("Infallible")
+}
+
 type Result<T, Error = VMError> = core::result::Result<T, Error>;
 
 pub struct TestSession {
@@ -83,12 +115,28 @@ impl TestSession {
     pub fn new() -> Self {
         // deploy a session with transfer & stake contract deployed
         // pass a list of accounts to fund
-        let mut network_session = NetworkSession::instantiate(vec![
+        let network_session = NetworkSession::instantiate(vec![
             (&*Self::PK_0, MOONLIGHT_BALANCE),
             (&*Self::PK_1, MOONLIGHT_BALANCE),
             (&*Self::PK_2, MOONLIGHT_BALANCE),
         ]);
 
+        Self::deploy_contracts(network_session)
+    }
+
+    /// Instantiate a session that doesn't verify the proofs of shielded
+    /// (Phoenix) transactions, for the tests executing them.
+    pub fn new_unverified() -> Self {
+        let network_session = NetworkSession::instantiate_unverified(vec![
+            (&*Self::PK_0, MOONLIGHT_BALANCE),
+            (&*Self::PK_1, MOONLIGHT_BALANCE),
+            (&*Self::PK_2, MOONLIGHT_BALANCE),
+        ]);
+
+        Self::deploy_contracts(network_session)
+    }
+
+    fn deploy_contracts(mut network_session: NetworkSession) -> Self {
         // deploy the Token contract
         network_session
             .deploy(
@@ -121,6 +169,30 @@ impl TestSession {
             )
             .expect("Deploying the holder contract should succeed");
 
+        // deploy the compliance contract, which is only used by the token
+        // once registered
+        network_session
+            .deploy(
+                COMPLIANCE_BYTECODE,
+                ContractData::builder()
+                    .owner(DEPLOYER)
+                    .init_arg(&TOKEN_ID)
+                    .contract_id(COMPLIANCE_ID),
+            )
+            .expect("Deploying the compliance contract should succeed");
+
+        // deploy the malicious contract, which re-enters the token when it
+        // receives tokens through `transfer_and_call`
+        network_session
+            .deploy(
+                MALICIOUS_BYTECODE,
+                ContractData::builder()
+                    .owner(DEPLOYER)
+                    .init_arg(&TOKEN_ID)
+                    .contract_id(MALICIOUS_ID),
+            )
+            .expect("Deploying the malicious contract should succeed");
+
         let mut session = Self {
             session: network_session,
         };
@@ -136,6 +208,10 @@ impl TestSession {
         session
     }
 
+    pub fn advance_block_height(&mut self, blocks: u64) {
+        self.session.advance_block_height(blocks);
+    }
+
     pub fn call_token<A, R>(
         &mut self,
         tx_sk: &AccountSecretKey,
@@ -153,6 +229,60 @@ impl TestSession {
             .icc_transaction(tx_sk, TOKEN_ID, fn_name, fn_arg)
     }
 
+    /// Call the token-contract like [`call_token`](Self::call_token), with
+    /// the given gas limit.
+    pub fn call_token_with_gas_limit<A, R>(
+        &mut self,
+        tx_sk: &AccountSecretKey,
+        fn_name: &str,
+        fn_arg: &A,
+        gas_limit: u64,
+    ) -> Result<CallReceipt<R>, ContractError>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session.icc_transaction_with_gas_limit(
+            tx_sk, TOKEN_ID, fn_name, fn_arg, gas_limit,
+        )
+    }
+
+    /// Call the token-contract with a shielded (Phoenix) transaction, paying
+    /// gas with the given `note` owned by the `phoenix_sk`.
+    ///
+    /// Note: the session needs to be created with
+    /// [`TestSession::new_unverified`].
+    pub fn phoenix_call_token<A, R>(
+        &mut self,
+        phoenix_sk: &PhoenixSecretKey,
+        note: Note,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> Result<CallReceipt<R>, ContractError>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session.phoenix_icc_transaction(
+            phoenix_sk, note, TOKEN_ID, fn_name, fn_arg,
+        )
+    }
+
+    /// Create a note of the given value owned by the `phoenix_pk`.
+    pub fn fund_phoenix(
+        &mut self,
+        phoenix_pk: &PhoenixPublicKey,
+        value: u64,
+    ) -> Note {
+        self.session.fund_phoenix(phoenix_pk, value)
+    }
+
     fn call_token_getter<R>(&mut self, fn_name: &str) -> CallReceipt<R>
     where
         R: Archive,
@@ -180,6 +310,61 @@ impl TestSession {
         ).as_str())
     }
 
+    /// Deploy another instance of the token-contract.
+    pub fn deploy_token(
+        &mut self,
+        contract_id: ContractId,
+        initial_accounts: Vec<(Account, u64)>,
+        ownership: Account,
+    ) {
+        self.session
+            .deploy(
+                TOKEN_BYTECODE,
+                ContractData::builder()
+                    .owner(DEPLOYER)
+                    .init_arg(&(initial_accounts, ownership))
+                    .contract_id(contract_id),
+            )
+            .expect("Deploying the token-contract should succeed");
+    }
+
+    pub fn call_contract<A, R>(
+        &mut self,
+        contract_id: ContractId,
+        tx_sk: &AccountSecretKey,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> Result<CallReceipt<R>, ContractError>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .icc_transaction(tx_sk, contract_id, fn_name, fn_arg)
+    }
+
+    pub fn query_contract<A, R>(
+        &mut self,
+        contract_id: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> R
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .direct_call(contract_id, fn_name, fn_arg)
+            .expect("call to pass")
+            .data
+    }
+
     pub fn call_holder<A, R>(
         &mut self,
         tx_sk: &AccountSecretKey,
@@ -197,6 +382,30 @@ impl TestSession {
             .icc_transaction(tx_sk, HOLDER_ID, fn_name, fn_arg)
     }
 
+    /// Query a function of the token-contract for the given currency.
+    pub fn query_currency<A, R>(
+        &mut self,
+        currency: CurrencyId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> R
+    where
+        A: Clone + for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .direct_call(TOKEN_ID, fn_name, &(currency, fn_arg.clone()))
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn currencies(&mut self) -> Vec<CurrencyId> {
+        self.call_token_getter("currencies").data
+    }
+
     pub fn account(&mut self, account: impl Into<Account>) -> AccountInfo {
         self.session
             .direct_call(TOKEN_ID, "account", &account.into())
@@ -211,6 +420,23 @@ impl TestSession {
             .data
     }
 
+    pub fn sanction_reason(
+        &mut self,
+        account: impl Into<Account>,
+    ) -> Option<Reason> {
+        self.session
+            .direct_call(TOKEN_ID, "sanction_reason", &account.into())
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn is_reserved(&mut self, account: impl Into<Account>) -> bool {
+        self.session
+            .direct_call(TOKEN_ID, "is_reserved", &account.into())
+            .expect("call to pass")
+            .data
+    }
+
     pub fn ownership(&mut self) -> Account {
         self.call_token_getter("ownership").data
     }
@@ -219,6 +445,76 @@ impl TestSession {
         self.call_token_getter("total_supply").data
     }
 
+    /// Query the sequence number of the last event emitted.
+    pub fn event_sequence(&mut self) -> u64 {
+        self.call_token_getter("event_sequence").data
+    }
+
+    /// Query the root of the Merkle tree over the balances.
+    pub fn balances_root(&mut self) -> [u8; 32] {
+        self.call_token_getter("balances_root").data
+    }
+
+    /// Query the proof of the balance of an account against the root of the
+    /// Merkle tree over the balances.
+    pub fn balance_proof(
+        &mut self,
+        account: impl Into<Account>,
+    ) -> Option<token::merkle::BalanceProof> {
+        self.session
+            .direct_call(TOKEN_ID, "balance_proof", &account.into())
+            .expect("call to pass")
+            .data
+    }
+
+    /// Query the breakdown of the supply.
+    pub fn supply_breakdown(&mut self) -> SupplyBreakdown {
+        self.call_token_getter("supply_breakdown").data
+    }
+
+    /// Check the invariants of a page of up to `limit` accounts, starting
+    /// after `after` with the running sum of `balances`.
+    pub fn verify_invariants(
+        &mut self,
+        after: Option<Account>,
+        balances: u64,
+        limit: u32,
+    ) -> InvariantReport {
+        self.session
+            .direct_call(
+                TOKEN_ID,
+                "verify_invariants",
+                &(after, balances, limit),
+            )
+            .expect("call to pass")
+            .data
+    }
+
+    /// Query the pending redemption requests together with their ids.
+    pub fn pending_redemptions(&mut self) -> Vec<(u64, RedemptionRequest)> {
+        self.call_token_getter("pending_redemptions").data
+    }
+
+    /// Query a hold by its id.
+    pub fn hold_data(&mut self, id: u64) -> Option<Hold> {
+        self.session
+            .direct_call(TOKEN_ID, "hold_data", &id)
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn balance_on_hold(&mut self, account: impl Into<Account>) -> u64 {
+        self.session
+            .direct_call(TOKEN_ID, "balance_on_hold", &account.into())
+            .expect("call to pass")
+            .data
+    }
+
+    /// Query the transfers pending clearing together with their ids.
+    pub fn pending_transfers(&mut self) -> Vec<(u64, PendingTransfer)> {
+        self.call_token_getter("pending_transfers").data
+    }
+
     /// Query the paused status of the EMT token contract.
     pub fn is_paused(&mut self) -> bool {
         self.call_token_getter("is_paused").data
@@ -230,6 +526,20 @@ impl TestSession {
         self.call_holder_getter::<u64>("tracked_balance").data
     }
 
+    /// Query the error returned to the malicious contract by its last call
+    /// to the token-contract, if the call failed.
+    pub fn malicious_attack_error(&mut self) -> Option<ContractError> {
+        self.query_contract(MALICIOUS_ID, "attack_error", &())
+    }
+
+    /// Query the nonce of the next call signed by the given key.
+    pub fn signed_call_nonce(&mut self, signer: AccountPublicKey) -> u64 {
+        self.session
+            .direct_call(TOKEN_ID, "signed_call_nonce", &signer)
+            .expect("call to pass")
+            .data
+    }
+
     pub fn allowance(
         &mut self,
         owner: impl Into<Account>,
@@ -240,4 +550,69 @@ impl TestSession {
             .expect("call to pass")
             .data
     }
+
+    pub fn can_transfer(
+        &mut self,
+        sender: impl Into<Account>,
+        receiver: impl Into<Account>,
+        value: u64,
+    ) -> TransferStatus {
+        self.session
+            .direct_call(
+                TOKEN_ID,
+                "can_transfer",
+                &(sender.into(), receiver.into(), value),
+            )
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn can_transfer_from(
+        &mut self,
+        spender: impl Into<Account>,
+        owner: impl Into<Account>,
+        receiver: impl Into<Account>,
+        value: u64,
+    ) -> TransferStatus {
+        self.session
+            .direct_call(
+                TOKEN_ID,
+                "can_transfer_from",
+                &(spender.into(), owner.into(), receiver.into(), value),
+            )
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn can_operator_send(
+        &mut self,
+        operator: impl Into<Account>,
+        holder: impl Into<Account>,
+        receiver: impl Into<Account>,
+        value: u64,
+    ) -> TransferStatus {
+        self.session
+            .direct_call(
+                TOKEN_ID,
+                "can_operator_send",
+                &(operator.into(), holder.into(), receiver.into(), value),
+            )
+            .expect("call to pass")
+            .data
+    }
+
+    pub fn is_operator_for(
+        &mut self,
+        operator: impl Into<Account>,
+        holder: impl Into<Account>,
+    ) -> bool {
+        self.session
+            .direct_call(
+                TOKEN_ID,
+                "is_operator_for",
+                &(operator.into(), holder.into()),
+            )
+            .expect("call to pass")
+            .data
+    }
 }
@@ -6,23 +6,36 @@
 
 use dusk_core::abi::ContractError;
 use dusk_core::abi::{ContractId, CONTRACT_ID_BYTES};
+use dusk_core::dusk;
 use dusk_core::signatures::bls::{
     PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
 };
 use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::phoenix::{
+    PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey,
+};
 use dusk_core::transfer::MoonlightTransactionEvent;
 
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 
-use emt_core::token::error;
 use emt_core::token::events;
-use emt_core::{Account, AccountInfo, ZERO_ADDRESS};
+use emt_core::token::{compact, error};
+use emt_core::{
+    Account, AccountAttributes, AccountInfo, CurrencyId, CurrencyMetadata,
+    Document, ExportedAccount, Hold, Jurisdiction, PendingTransfer,
+    RedemptionRequest, ReserveAttestation, SignedCall, SupplyBreakdown,
+    TransferStatus, DEFAULT_CURRENCY, ZERO_ADDRESS,
+};
+
+use emt_tests::network::CHAIN_ID;
 
 pub mod instantiate;
+use instantiate::event_data;
 use instantiate::{
-    TestSession, HOLDER_ID, INITIAL_BALANCE, INITIAL_HOLDER_BALANCE,
-    INITIAL_SUPPLY,
+    TestSession, COMPLIANCE_ID, HOLDER_ID, INITIAL_BALANCE,
+    INITIAL_HOLDER_BALANCE, INITIAL_OWNERSHIP_BALANCE, INITIAL_SUPPLY,
+    MALICIOUS_ID, TEST_REASON, TOKEN_ID,
 };
 
 #[test]
@@ -170,6 +183,103 @@ fn transfer_to_contract() {
     );
 }
 
+/// Test that transfer events are emitted in the compact encoding.
+#[test]
+fn compact_transfer_event() {
+    const TRANSFERRED_AMOUNT: u64 = 42;
+
+    let mut session = TestSession::new();
+
+    let sender = Account::from(*TestSession::PK_1);
+    let receiver = Account::from(HOLDER_ID);
+    let sequence = session.event_sequence() + 1;
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, TRANSFERRED_AMOUNT),
+        )
+        .expect("Call should pass");
+
+    let event = receipt
+        .events
+        .iter()
+        .find(|event| event.topic == events::Transfer::TRANSFER_TOPIC)
+        .expect("A transfer event should be emitted");
+
+    // version, flags, sequence, compressed key, contract id and value
+    assert_eq!(event.data.len(), 1 + 1 + 8 + 97 + 33 + 8);
+    assert_eq!(event.data[0], compact::VERSION);
+    assert_eq!(
+        compact::decode_transfer(&event.data),
+        Ok((
+            sequence,
+            DEFAULT_CURRENCY,
+            events::Transfer {
+                sender,
+                spender: None,
+                receiver,
+                value: TRANSFERRED_AMOUNT,
+                reason: None,
+            }
+        ))
+    );
+
+    let mut unsupported = event.data.clone();
+    unsupported[0] = compact::VERSION + 1;
+    assert_eq!(
+        compact::decode_transfer(&unsupported),
+        Err(compact::DecodeError::UnsupportedVersion(
+            compact::VERSION + 1
+        ))
+    );
+    assert_eq!(
+        compact::decode_transfer(&event.data[..event.data.len() - 1]),
+        Err(compact::DecodeError::InvalidLength)
+    );
+}
+
+/// Test that the events are numbered in the order they are emitted.
+#[test]
+fn event_sequence() {
+    const VALUE: u64 = 10;
+
+    let mut session = TestSession::new();
+    let spender = Account::from(*TestSession::PK_2);
+
+    let sequence = session.event_sequence();
+    assert!(sequence > 0, "The initial mints should be numbered");
+
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_1, "transfer", &(spender, VALUE))
+        .expect("Call should pass");
+    let event = receipt
+        .events
+        .iter()
+        .find(|event| event.topic == events::Transfer::TRANSFER_TOPIC)
+        .expect("A transfer event should be emitted");
+    let (transfer_sequence, _, _) = compact::decode_transfer(&event.data)
+        .expect("The transfer event should decode");
+    assert_eq!(transfer_sequence, sequence + 1);
+
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_1, "approve", &(spender, VALUE))
+        .expect("Call should pass");
+    let event = receipt
+        .events
+        .iter()
+        .find(|event| event.topic == events::Approve::APPROVE_TOPIC)
+        .expect("An approve event should be emitted");
+    let approve_event = rkyv::from_bytes::<
+        events::SequencedEvent<events::Approve>,
+    >(&event.data)
+    .expect("The approve event should deserialize");
+    assert_eq!(approve_event.sequence, sequence + 2);
+
+    assert_eq!(session.event_sequence(), sequence + 2);
+}
+
 /// Test a token transfer and call from the deploy account to the test contract
 /// account.
 #[test]
@@ -248,7 +358,7 @@ fn transfer_and_call_to_contract() {
             )
         } else if event.topic == "transfer" {
             let transfer_event =
-                rkyv::from_bytes::<events::Transfer>(&event.data).unwrap();
+                compact::decode_transfer(&event.data).unwrap().2;
 
             assert!(
                 transfer_event.sender == HOLDER_ID.into(),
@@ -279,6 +389,177 @@ fn transfer_and_call_to_contract() {
     );
 }
 
+/// Test that the receiver of a `transfer_and_call` can't re-enter the
+/// token-contract to modify balances or allowances while it is called.
+#[test]
+fn transfer_and_call_reentrancy() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+
+    let mut session = TestSession::new();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let malicious = Account::from(MALICIOUS_ID);
+
+    // the malicious contract may spend tokens of the first account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "approve",
+            &(malicious, INITIAL_BALANCE),
+        )
+        .expect("Approving should succeed");
+
+    let attacks = [
+        ContractCall::new(TOKEN_ID, "transfer")
+            .with_args(&(account_1, TRANSFERRED_AMOUNT)),
+        ContractCall::new(TOKEN_ID, "approve")
+            .with_args(&(account_1, u64::MAX)),
+        ContractCall::new(TOKEN_ID, "transfer_from").with_args(&(
+            account_1,
+            malicious,
+            TRANSFERRED_AMOUNT,
+        )),
+        ContractCall::new(TOKEN_ID, "transfer_and_call").with_args(&(
+            TRANSFERRED_AMOUNT,
+            ContractCall::new(MALICIOUS_ID, "token_received"),
+        )),
+    ];
+
+    for (i, attack) in attacks.into_iter().enumerate() {
+        let attack = attack.expect("Creating contract call should succeed");
+        session
+            .call_contract::<_, ()>(
+                MALICIOUS_ID,
+                &*TestSession::SK_0,
+                "set_attack",
+                &(attack.fn_name, attack.fn_args),
+            )
+            .expect("Setting the attack should succeed");
+
+        session
+            .call_token::<_, ()>(
+                &*TestSession::SK_1,
+                "transfer_and_call",
+                &(
+                    TRANSFERRED_AMOUNT,
+                    ContractCall::new(MALICIOUS_ID, "token_received"),
+                ),
+            )
+            .expect("The transfer should succeed");
+
+        if let Some(ContractError::Panic(panic_msg)) =
+            session.malicious_attack_error()
+        {
+            assert_eq!(panic_msg, error::REENTRANT_CALL);
+        } else {
+            panic!("Expected the re-entrant call to panic");
+        }
+
+        let transferred = (i as u64 + 1) * TRANSFERRED_AMOUNT;
+        assert_eq!(
+            session.account(account_1).balance,
+            INITIAL_BALANCE - transferred
+        );
+        assert_eq!(session.account(malicious).balance, transferred);
+        assert_eq!(session.allowance(account_1, malicious), INITIAL_BALANCE);
+        assert_eq!(session.allowance(malicious, account_1), 0);
+    }
+
+    // outside of a transfer_and_call, the same call is allowed
+    let attack = ContractCall::new(TOKEN_ID, "transfer")
+        .with_args(&(account_1, TRANSFERRED_AMOUNT))
+        .expect("Creating contract call should succeed");
+    session
+        .call_contract::<_, ()>(
+            MALICIOUS_ID,
+            &*TestSession::SK_0,
+            "set_attack",
+            &(attack.fn_name, attack.fn_args),
+        )
+        .expect("Setting the attack should succeed");
+    session
+        .call_contract::<_, ()>(
+            MALICIOUS_ID,
+            &*TestSession::SK_0,
+            "token_received",
+            &(),
+        )
+        .expect("Calling the malicious contract should succeed");
+
+    assert!(session.malicious_attack_error().is_none());
+    assert_eq!(
+        session.account(malicious).balance,
+        3 * TRANSFERRED_AMOUNT,
+        "The call should transfer back the tokens"
+    );
+}
+
+/// Test that the receiver of a `transfer_and_call` can't re-enter the
+/// token-contract in another currency either.
+#[test]
+fn transfer_and_call_reentrancy_other_currency() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+    const CURRENCY: CurrencyId = 1;
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let malicious = Account::from(MALICIOUS_ID);
+    let metadata = CurrencyMetadata {
+        name: String::from("Electronic Money Euro"),
+        symbol: String::from("EURE"),
+        decimals: 2,
+    };
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "create_currency",
+            &(CURRENCY, metadata, ownership),
+        )
+        .expect("Creating the currency should succeed");
+
+    let attack = ContractCall::new(TOKEN_ID, "currency_approve")
+        .with_args(&(CURRENCY, (account_1, u64::MAX)))
+        .expect("Creating contract call should succeed");
+    session
+        .call_contract::<_, ()>(
+            MALICIOUS_ID,
+            &*TestSession::SK_0,
+            "set_attack",
+            &(attack.fn_name, attack.fn_args),
+        )
+        .expect("Setting the attack should succeed");
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer_and_call",
+            &(
+                TRANSFERRED_AMOUNT,
+                ContractCall::new(MALICIOUS_ID, "token_received"),
+            ),
+        )
+        .expect("The transfer should succeed");
+
+    if let Some(ContractError::Panic(panic_msg)) =
+        session.malicious_attack_error()
+    {
+        assert_eq!(panic_msg, error::REENTRANT_CALL);
+    } else {
+        panic!("Expected the re-entrant call to panic");
+    }
+
+    assert_eq!(session.account(malicious).balance, TRANSFERRED_AMOUNT);
+    assert_eq!(
+        session.query_currency::<_, u64>(
+            CURRENCY,
+            "currency_allowance",
+            &(malicious, account_1)
+        ),
+        0
+    );
+}
+
 /// Test a token transfer from the HOLDER_ID contract account to the deploy
 /// account.
 #[test]
@@ -319,7 +600,7 @@ fn transfer_from_contract() {
             )
         } else if event.topic == "transfer" {
             let transfer_event =
-                rkyv::from_bytes::<events::Transfer>(&event.data).unwrap();
+                compact::decode_transfer(&event.data).unwrap().2;
 
             assert!(
                 transfer_event.sender == HOLDER_ID.into(),
@@ -523,6 +804,122 @@ fn renounce_ownership() {
     );
 }
 
+/// Test that reserved addresses can not be credited
+/// Test that reserved addresses can not receive the ownership
+/// Test adding a burn address to the reserved addresses
+#[test]
+fn reserved_addresses() {
+    const VALUE: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = TestSession::new();
+    let token = Account::from(TOKEN_ID);
+    let burn_address = Account::from(*TestSession::PK_2);
+
+    assert!(session.is_reserved(ZERO_ADDRESS));
+    assert!(session.is_reserved(token));
+    assert!(!session.is_reserved(burn_address));
+
+    for receiver in [ZERO_ADDRESS, token] {
+        let receipt = session.call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, VALUE),
+        );
+
+        if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+            assert_eq!(panic_msg, error::RESERVED_ADDRESS);
+        } else {
+            panic!("Expected a panic error");
+        }
+
+        let receipt = session.call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "mint",
+            &(receiver, VALUE, TEST_REASON),
+        );
+
+        if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+            assert_eq!(panic_msg, error::RESERVED_ADDRESS);
+        } else {
+            panic!("Expected a panic error");
+        }
+
+        let receipt = session.call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "force_transfer",
+            &(
+                Account::from(*TestSession::PK_1),
+                receiver,
+                VALUE,
+                TEST_REASON,
+            ),
+        );
+
+        if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+            assert_eq!(panic_msg, error::RESERVED_ADDRESS);
+        } else {
+            panic!("Expected a panic error");
+        }
+
+        let receipt = session.call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "transfer_ownership",
+            &receiver,
+        );
+
+        if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+            assert_eq!(panic_msg, error::RESERVED_ADDRESS);
+        } else {
+            panic!("Expected a panic error");
+        }
+    }
+
+    // only the ownership can add burn addresses
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "add_reserved_address",
+        &burn_address,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "add_reserved_address",
+            &burn_address,
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::ReservedAddressAdded::TOPIC
+            && event_data::<events::ReservedAddressAdded>(&event.data)
+                == events::ReservedAddressAdded {
+                    account: burn_address,
+                }
+    }));
+    assert!(session.is_reserved(burn_address));
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "transfer",
+        &(burn_address, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::RESERVED_ADDRESS);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    assert_eq!(session.account(burn_address).balance, 0);
+    assert_eq!(session.account(*TestSession::PK_1).balance, INITIAL_BALANCE);
+}
+
 /// Test mint with ownership sk
 /// Test mint with wrong sk
 /// Test mint with overflow
@@ -543,7 +940,7 @@ fn test_mint() {
         .call_token::<_, ()>(
             &*TestSession::SK_0,
             "mint",
-            &(mint_receiver, mint_amount),
+            &(mint_receiver, mint_amount, TEST_REASON),
         )
         .expect("Call should pass");
 
@@ -552,7 +949,7 @@ fn test_mint() {
     receipt.events.iter().any(|event| {
         if event.topic == "mint" {
             let transfer_event =
-                rkyv::from_bytes::<events::Transfer>(&event.data).unwrap();
+                compact::decode_transfer(&event.data).unwrap().2;
 
             assert!(
                 transfer_event.sender == ZERO_ADDRESS,
@@ -566,6 +963,11 @@ fn test_mint() {
                 transfer_event.value, mint_amount,
                 "The transferred amount should be the mint_amount"
             );
+            assert_eq!(
+                transfer_event.reason,
+                Some(TEST_REASON),
+                "The reason should be the one given by the ownership"
+            );
             true
         } else {
             false
@@ -580,7 +982,7 @@ fn test_mint() {
     let receipt = session.call_token::<_, ()>(
         &*TestSession::SK_0,
         "mint",
-        &(mint_receiver, too_much),
+        &(mint_receiver, too_much, TEST_REASON),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
@@ -592,7 +994,7 @@ fn test_mint() {
     let receipt = session.call_token::<_, ()>(
         &*TestSession::SK_2,
         "mint",
-        &(mint_receiver, mint_amount),
+        &(mint_receiver, mint_amount, TEST_REASON),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
@@ -602,102 +1004,156 @@ fn test_mint() {
     }
 }
 
-/// Test burn with ownership sk
-/// Test burn with wrong sk
-/// Test burn with balance too low / underflow
+/// Test that only the ownership can set the minter
+/// Test minting with the minter sk
+/// Test that the minter can't call other functions of the ownership
+/// Test renouncing the minter role
 #[test]
-fn test_burn() {
+fn test_minter() {
+    const MINT_AMOUNT: u64 = 1000;
+
     let mut session = TestSession::new();
-    let burn_amount = 1000;
+    let minter = Account::from(*TestSession::PK_2);
+    let receiver = Account::from(*TestSession::PK_1);
 
-    session
-        .call_token::<_, ()>(&*TestSession::SK_0, "burn", &burn_amount)
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "set_minter",
+        &Some(minter),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_0, "set_minter", &Some(minter))
         .expect("Call should pass");
 
-    assert_eq!(session.total_supply(), INITIAL_SUPPLY - burn_amount);
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::MinterUpdated::TOPIC
+            && event_data::<events::MinterUpdated>(&event.data)
+                == events::MinterUpdated {
+                    minter: Some(minter),
+                }
+    }));
+    assert_eq!(
+        session.query_contract::<_, Option<Account>>(TOKEN_ID, "minter", &()),
+        Some(minter)
+    );
 
-    // burn more than the ownership account has
-    let burn_amount = u64::MAX;
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "mint",
+            &(receiver, MINT_AMOUNT, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY + MINT_AMOUNT);
+    assert_eq!(
+        session.account(receiver).balance,
+        INITIAL_BALANCE + MINT_AMOUNT
+    );
 
+    // the minter can only mint
     let receipt =
-        session.call_token::<_, ()>(&*TestSession::SK_0, "burn", &burn_amount);
+        session.call_token::<_, ()>(&*TestSession::SK_2, "pause", &());
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, error::BALANCE_TOO_LOW);
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
     } else {
         panic!("Expected a panic error");
     }
 
-    // unauthorized account
-    let receipt =
-        session.call_token::<_, ()>(&*TestSession::SK_2, "burn", &burn_amount);
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "set_minter",
+        &Some(receiver),
+    );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
         assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
     } else {
         panic!("Expected a panic error");
     }
-}
 
-/// Test pause
-/// Test transfer from deploy_sk while paused
-/// Test unpause
-/// Test transfer from deploy_sk after unpausing
-/// Test pause with wrong sk
-#[test]
-fn test_pause() {
-    const VALUE: u64 = INITIAL_BALANCE - 1;
+    // only the minter can renounce its role
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "renounce_minter",
+        &(),
+    );
 
-    let mut session = TestSession::new();
-    let account_2 = Account::from(*TestSession::PK_2);
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
 
     session
-        .call_token::<_, ()>(&*TestSession::SK_0, "toggle_pause", &())
+        .call_token::<_, ()>(&*TestSession::SK_2, "renounce_minter", &())
         .expect("Call should pass");
 
-    assert_eq!(session.is_paused(), true);
+    assert_eq!(
+        session.query_contract::<_, Option<Account>>(TOKEN_ID, "minter", &()),
+        None
+    );
 
     let receipt = session.call_token::<_, ()>(
-        &*TestSession::SK_1,
-        "transfer",
-        &(account_2, VALUE),
+        &*TestSession::SK_2,
+        "mint",
+        &(receiver, MINT_AMOUNT, TEST_REASON),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, error::PAUSED_MESSAGE);
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
     } else {
         panic!("Expected a panic error");
     }
+}
 
-    assert_eq!(
-        session.account(*TestSession::PK_1).balance,
-        INITIAL_BALANCE,
-        "The deployed account should have the initial balance"
-    );
-
-    assert_eq!(
-        session.account(account_2).balance,
-        0,
-        "The account to transfer to should have no balance"
-    );
-
-    session
-        .call_token::<_, ()>(&*TestSession::SK_0, "toggle_pause", &())
-        .expect("Call should pass");
-
-    assert_eq!(session.is_paused(), false);
+/// Test burn with ownership sk
+/// Test burn with wrong sk
+/// Test burn with balance too low / underflow
+#[test]
+fn test_burn() {
+    let mut session = TestSession::new();
+    let burn_amount = 1000;
 
     session
         .call_token::<_, ()>(
-            &*TestSession::SK_1,
-            "transfer",
-            &(account_2, VALUE),
+            &*TestSession::SK_0,
+            "burn",
+            &(burn_amount, TEST_REASON),
         )
         .expect("Call should pass");
 
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY - burn_amount);
+
+    // burn more than the ownership account has
+    let burn_amount = u64::MAX;
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "burn",
+        &(burn_amount, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::BALANCE_TOO_LOW);
+    } else {
+        panic!("Expected a panic error");
+    }
+
     // unauthorized account
-    let receipt =
-        session.call_token::<_, ()>(&*TestSession::SK_2, "toggle_pause", &());
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "burn",
+        &(burn_amount, TEST_REASON),
+    );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
         assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
@@ -706,66 +1162,61 @@ fn test_pause() {
     }
 }
 
-/// Test force transfer
-/// Test force transfer with balance too low
-/// Test force transfer with wrong sk
-/// TODO: test force transfer circumventing pause, sanction, etc.
+/// Test controller redeem from a blocked account
+/// Test controller redeem with balance too low
+/// Test controller redeem with wrong sk
 #[test]
-fn test_force_transfer() {
-    const VALUE: u64 = INITIAL_BALANCE - 1;
+fn test_controller_redeem() {
+    const REDEEM_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
     let mut session = TestSession::new();
-    let account_1 = Account::from(*TestSession::PK_1);
-    let account_2 = Account::from(*TestSession::PK_2);
-    let ownership_account = Account::from(*TestSession::PK_0);
+    let blocked_account = Account::from(*TestSession::PK_1);
 
-    // Make a normal transfer from deploy account to the test account
     session
         .call_token::<_, ()>(
-            &*TestSession::SK_1,
-            "transfer",
-            &(account_2, VALUE),
+            &*TestSession::SK_0,
+            "block",
+            &(blocked_account, TEST_REASON),
         )
         .expect("Call should pass");
 
-    assert_eq!(
-        session.account(account_1).balance,
-        INITIAL_BALANCE - VALUE,
-        "The deployed account should have the transferred amount subtracted"
-    );
-    assert_eq!(
-        session.account(account_2).balance,
-        VALUE,
-        "The test account should have the transferred amount"
-    );
-
-    // Force transfer from test account to ownership account
-    let obliged_sender = account_2;
-    session
+    let receipt = session
         .call_token::<_, ()>(
             &*TestSession::SK_0,
-            "force_transfer",
-            &(obliged_sender, ownership_account, VALUE),
+            "controller_redeem",
+            &(blocked_account, REDEEM_AMOUNT, TEST_REASON),
         )
         .expect("Call should pass");
 
+    let redeem_event = receipt
+        .events
+        .iter()
+        .find(|event| event.topic == events::Transfer::CONTROLLER_REDEEM_TOPIC)
+        .expect("The controller redeem event should be emitted");
+    let redeem_event = compact::decode_transfer(&redeem_event.data).unwrap().2;
     assert_eq!(
-        session.account(account_2).balance,
-        0,
-        "The test account should have the transferred amount subtracted"
+        redeem_event,
+        events::Transfer {
+            sender: blocked_account,
+            spender: None,
+            receiver: ZERO_ADDRESS,
+            value: REDEEM_AMOUNT,
+            reason: Some(TEST_REASON),
+        }
     );
 
     assert_eq!(
-        session.account(ownership_account).balance,
-        INITIAL_BALANCE + VALUE,
-        "The ownership account should have the transferred amount added"
+        session.account(blocked_account).balance,
+        INITIAL_BALANCE - REDEEM_AMOUNT,
+        "The blocked account should have the redeemed amount subtracted"
     );
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY - REDEEM_AMOUNT);
 
-    // Force transfer from test account to ownership account again (balance
-    // will be too low)
+    // redeem more than the account has
     let receipt = session.call_token::<_, ()>(
         &*TestSession::SK_0,
-        "force_transfer",
-        &(obliged_sender, ownership_account, VALUE),
+        "controller_redeem",
+        &(blocked_account, INITIAL_BALANCE, TEST_REASON),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
@@ -775,11 +1226,10 @@ fn test_force_transfer() {
     }
 
     // unauthorized account
-    let obliged_sender = Account::from(ownership_account);
     let receipt = session.call_token::<_, ()>(
         &*TestSession::SK_2,
-        "force_transfer",
-        &(obliged_sender, account_2, VALUE),
+        "controller_redeem",
+        &(blocked_account, REDEEM_AMOUNT, TEST_REASON),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
@@ -787,153 +1237,310 @@ fn test_force_transfer() {
     } else {
         panic!("Expected a panic error");
     }
+
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY - REDEEM_AMOUNT);
 }
 
-/// Test block account
-/// Test unfreezing blocked account (fail)
-/// Test transfer to blocked account (fail)
-/// Test transfer from blocked account (fail)
-/// Test freezing blocked account (overriding is allowed)
-/// Test transfer to frozen account (allow)
-/// Test unblocking frozen account (fail)
-/// Test wrong sk for unblock & unfreeze (fail)
-/// Test unfreezing frozen account
-/// Test transfer after unfrozen
+/// Test redemption request
+/// Test settling a redemption request
+/// Test rejecting a redemption request
+/// Test settling with wrong sk
+/// Test settling an unknown redemption request
 #[test]
-fn test_sanctions() {
-    // TODO: unify transfer logic in the contract so that this implicitly checks
-    // the invariants of transferFrom and any other potential function
-    // leading to a "transfer" that updates the balance
+fn test_redemption() {
+    const REDEEM_AMOUNT: u64 = INITIAL_BALANCE / 4;
+    const PAYOUT_REFERENCE: [u8; 32] = [1; 32];
+    const SETTLEMENT_REFERENCE: [u8; 32] = [2; 32];
 
-    const VALUE: u64 = INITIAL_BALANCE / 3;
     let mut session = TestSession::new();
-    let account_1 = Account::from(*TestSession::PK_1);
-    let blocked_account = Account::from(*TestSession::PK_2);
+    let holder = Account::from(*TestSession::PK_1);
 
-    // Transfer VALUE to test account
-    session
-        .call_token::<_, ()>(
+    // request a redemption
+    let receipt = session
+        .call_token::<_, u64>(
             &*TestSession::SK_1,
-            "transfer",
-            &(blocked_account, VALUE),
+            "request_redemption",
+            &(REDEEM_AMOUNT, PAYOUT_REFERENCE),
         )
-        .expect("Call should pass")
-        .data;
-
-    // Block test account
-    session
-        .call_token::<_, ()>(&*TestSession::SK_0, "block", &blocked_account)
         .expect("Call should pass");
+    let settled_id = receipt.data;
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::RedemptionRequested::TOPIC
+            && event_data::<events::RedemptionRequested>(&event.data)
+                == events::RedemptionRequested {
+                    id: settled_id,
+                    holder,
+                    value: REDEEM_AMOUNT,
+                    payout_reference: PAYOUT_REFERENCE,
+                }
+    }));
 
     assert_eq!(
-        session
-            .call_token::<_, bool>(
-                &*TestSession::SK_2,
-                "blocked",
-                &blocked_account
-            )
-            .expect("Querying the state should succeed")
-            .data,
-        true
+        session.account(holder).balance,
+        INITIAL_BALANCE - REDEEM_AMOUNT,
+        "The redeemed amount should be held in escrow"
+    );
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY);
+    assert_eq!(
+        session.pending_redemptions(),
+        vec![(
+            settled_id,
+            RedemptionRequest {
+                holder,
+                value: REDEEM_AMOUNT,
+                payout_reference: PAYOUT_REFERENCE,
+            }
+        )]
     );
 
-    // Unfreeze blocked test account
+    // unauthorized account
     let receipt = session.call_token::<_, ()>(
-        &*TestSession::SK_0,
-        "unfreeze",
-        &blocked_account,
+        &*TestSession::SK_1,
+        "settle_redemption",
+        &(settled_id, SETTLEMENT_REFERENCE),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, "The account is not frozen");
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
     } else {
         panic!("Expected a panic error");
     }
 
-    // Transfer VALUE to test account
+    // settle the redemption
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "settle_redemption",
+            &(settled_id, SETTLEMENT_REFERENCE),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::RedemptionSettled::TOPIC));
+    assert!(
+        receipt.events.iter().any(|event| {
+            event.topic == events::Transfer::BURN_TOPIC
+                && compact::decode_transfer(&event.data).map(|(_, _, t)| t)
+                    == Ok(events::Transfer {
+                        sender: holder,
+                        spender: None,
+                        receiver: ZERO_ADDRESS,
+                        value: REDEEM_AMOUNT,
+                        reason: None,
+                    })
+        }),
+        "Settling should emit a burn event"
+    );
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY - REDEEM_AMOUNT);
+    assert!(session.pending_redemptions().is_empty());
+
+    // settling twice fails
     let receipt = session.call_token::<_, ()>(
-        &*TestSession::SK_1,
-        "transfer",
-        &(blocked_account, VALUE),
+        &*TestSession::SK_0,
+        "settle_redemption",
+        &(settled_id, SETTLEMENT_REFERENCE),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, error::BLOCKED);
+        assert_eq!(panic_msg, error::REDEMPTION_NOT_FOUND);
     } else {
         panic!("Expected a panic error");
     }
 
-    // Transfer VALUE from test account
-    let receipt = session.call_token::<_, ()>(
-        &*TestSession::SK_2,
-        "transfer",
-        &(account_1, VALUE),
+    // request another redemption and reject it
+    let rejected_id = session
+        .call_token::<_, u64>(
+            &*TestSession::SK_1,
+            "request_redemption",
+            &(REDEEM_AMOUNT, PAYOUT_REFERENCE),
+        )
+        .expect("Call should pass")
+        .data;
+    assert_ne!(settled_id, rejected_id);
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "reject_redemption",
+            &(rejected_id, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::RedemptionRejected::TOPIC));
+    assert_eq!(
+        session.account(holder).balance,
+        INITIAL_BALANCE - REDEEM_AMOUNT,
+        "The escrowed amount should be returned to the holder"
+    );
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY - REDEEM_AMOUNT);
+    assert!(session.pending_redemptions().is_empty());
+
+    // request more than the balance
+    let receipt = session.call_token::<_, u64>(
+        &*TestSession::SK_1,
+        "request_redemption",
+        &(INITIAL_BALANCE, PAYOUT_REFERENCE),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, error::BLOCKED);
+        assert_eq!(panic_msg, error::BALANCE_TOO_LOW);
     } else {
         panic!("Expected a panic error");
     }
+}
 
-    // Freeze test account
-    let frozen_account = blocked_account;
-    session
-        .call_token::<_, ()>(&*TestSession::SK_0, "freeze", &frozen_account)
-        .expect("Call should pass");
+/// Test putting funds on hold
+/// Test that held funds can not be transferred
+/// Test executing a hold partially
+/// Test releasing a hold
+/// Test executing or releasing a hold with an unauthorized account
+/// Test putting funds on hold as an operator of the payer
+#[test]
+fn test_hold() {
+    const HOLD_VALUE: u64 = INITIAL_BALANCE / 2;
+    const EXECUTE_VALUE: u64 = HOLD_VALUE / 2;
 
-    assert_eq!(
-        session
-            .call_token::<_, bool>(
-                &*TestSession::SK_2,
-                "frozen",
-                &frozen_account
-            )
-            .expect("Querying the state should succeed")
-            .data,
-        true
-    );
+    let mut session = TestSession::new();
+    let notary = Account::from(*TestSession::PK_0);
+    let payer = Account::from(*TestSession::PK_1);
+    let payee = Account::from(*TestSession::PK_2);
 
-    // Transfer VALUE to test account
-    session
-        .call_token::<_, ()>(
+    // put funds on hold
+    let receipt = session
+        .call_token::<_, u64>(
             &*TestSession::SK_1,
-            "transfer",
-            &(frozen_account, VALUE),
+            "hold",
+            &(payee, notary, HOLD_VALUE, 0u64),
         )
-        .expect("Transfer to frozen account should succeed");
+        .expect("Call should pass");
+    let executed_id = receipt.data;
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::HoldCreated::TOPIC
+            && event_data::<events::HoldCreated>(&event.data)
+                == events::HoldCreated {
+                    id: executed_id,
+                    payer,
+                    payee,
+                    notary,
+                    value: HOLD_VALUE,
+                    expiration: 0,
+                }
+    }));
+    assert_eq!(
+        session.hold_data(executed_id),
+        Some(Hold {
+            payer,
+            payee,
+            notary,
+            value: HOLD_VALUE,
+            expiration: 0,
+        })
+    );
+    assert_eq!(session.balance_on_hold(payer), HOLD_VALUE);
+    assert_eq!(session.account(payer).balance, INITIAL_BALANCE);
+    assert_eq!(
+        session.account(payer).spendable(),
+        INITIAL_BALANCE - HOLD_VALUE
+    );
 
-    // Transfer VALUE from test account
+    // held funds can not be transferred
     let receipt = session.call_token::<_, ()>(
-        &*TestSession::SK_2,
+        &*TestSession::SK_1,
         "transfer",
-        &(account_1, VALUE),
+        &(payee, INITIAL_BALANCE),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, error::FROZEN);
+        assert_eq!(panic_msg, error::BALANCE_TOO_LOW);
     } else {
         panic!("Expected a panic error");
     }
 
-    // Unblock frozen test account
+    // only the notary can execute the hold
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "execute_hold",
+        &(executed_id, EXECUTE_VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // execute part of the hold, the rest is released
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "execute_hold",
+            &(executed_id, EXECUTE_VALUE),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::HoldExecuted::TOPIC));
+    assert!(
+        receipt.events.iter().any(|event| {
+            event.topic == events::Transfer::TRANSFER_TOPIC
+                && compact::decode_transfer(&event.data).map(|(_, _, t)| t)
+                    == Ok(events::Transfer {
+                        sender: payer,
+                        spender: None,
+                        receiver: payee,
+                        value: EXECUTE_VALUE,
+                        reason: None,
+                    })
+        }),
+        "Executing a hold should emit a transfer event"
+    );
+    assert_eq!(session.hold_data(executed_id), None);
+    assert_eq!(session.balance_on_hold(payer), 0);
+    assert_eq!(
+        session.account(payer).balance,
+        INITIAL_BALANCE - EXECUTE_VALUE
+    );
+    assert_eq!(session.account(payee).balance, EXECUTE_VALUE);
+
+    // executing twice fails
     let receipt = session.call_token::<_, ()>(
         &*TestSession::SK_0,
-        "unblock",
-        &frozen_account,
+        "execute_hold",
+        &(executed_id, EXECUTE_VALUE),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
-        assert_eq!(panic_msg, "The account is not blocked");
+        assert_eq!(panic_msg, error::HOLD_NOT_FOUND);
     } else {
         panic!("Expected a panic error");
     }
 
-    // Unauthorized account
+    // the ownership puts funds on hold on behalf of the payer
+    let released_id = session
+        .call_token::<_, u64>(
+            &*TestSession::SK_0,
+            "hold_from",
+            &(payer, payee, notary, HOLD_VALUE, 0u64),
+        )
+        .expect("Call should pass")
+        .data;
+    assert_ne!(executed_id, released_id);
+    assert_eq!(session.balance_on_hold(payer), HOLD_VALUE);
+
+    // the payer can not release a hold that has not expired
     let receipt = session.call_token::<_, ()>(
-        &*TestSession::SK_2,
-        "unblock",
-        &frozen_account,
+        &*TestSession::SK_1,
+        "release_hold",
+        &released_id,
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
@@ -942,10 +1549,27 @@ fn test_sanctions() {
         panic!("Expected a panic error");
     }
 
-    let receipt = session.call_token::<_, ()>(
+    // the payee can release the hold
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_2, "release_hold", &released_id)
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::HoldReleased::TOPIC));
+    assert_eq!(session.hold_data(released_id), None);
+    assert_eq!(session.balance_on_hold(payer), 0);
+    assert_eq!(
+        session.account(payer).balance,
+        INITIAL_BALANCE - EXECUTE_VALUE
+    );
+
+    // only the operators of the payer can put funds on hold on its behalf
+    let receipt = session.call_token::<_, u64>(
         &*TestSession::SK_2,
-        "unfreeze",
-        &frozen_account,
+        "hold_from",
+        &(payer, payee, notary, HOLD_VALUE, 0u64),
     );
 
     if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
@@ -954,17 +1578,2997 @@ fn test_sanctions() {
         panic!("Expected a panic error");
     }
 
-    // Unfreeze test account
     session
-        .call_token::<_, ()>(&*TestSession::SK_0, "unfreeze", &frozen_account)
-        .expect("Unfreezing should succeed");
+        .call_token::<_, ()>(&*TestSession::SK_1, "authorize_operator", &payee)
+        .expect("Call should pass");
 
-    // Transfer VALUE from test account
-    session
-        .call_token::<_, ()>(
+    let operator_id = session
+        .call_token::<_, u64>(
             &*TestSession::SK_2,
-            "transfer",
-            &(account_1, VALUE),
+            "hold_from",
+            &(payer, payee, notary, HOLD_VALUE, 0u64),
         )
-        .expect("Transfer should succeed again");
+        .expect("Call should pass")
+        .data;
+    assert_eq!(
+        session.hold_data(operator_id).map(|hold| hold.payer),
+        Some(payer)
+    );
+    assert_eq!(session.balance_on_hold(payer), HOLD_VALUE);
+}
+
+/// Test transfer with a payment reference
+/// Test transfer_from with a payment reference
+/// Test transfer with a memo exceeding the maximum size
+#[test]
+fn test_transfer_with_reference() {
+    const VALUE: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = TestSession::new();
+    let sender = Account::from(*TestSession::PK_1);
+    let spender = Account::from(*TestSession::PK_0);
+    let receiver = Account::from(*TestSession::PK_2);
+    let memo = b"E2E-2024-0001".to_vec();
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer_with_reference",
+            &(receiver, VALUE, memo.clone()),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::Transfer::TRANSFER_TOPIC));
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::TransferReference::TOPIC
+            && event_data::<events::TransferReference>(&event.data)
+                == events::TransferReference {
+                    sender,
+                    spender: None,
+                    receiver,
+                    value: VALUE,
+                    memo: memo.clone(),
+                }
+    }));
+    assert_eq!(session.account(receiver).balance, VALUE);
+
+    // transfer_from with a reference
+    session
+        .call_token::<_, ()>(&*TestSession::SK_1, "approve", &(spender, VALUE))
+        .expect("Call should pass");
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "transfer_from_with_reference",
+            &(sender, receiver, VALUE, memo.clone()),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::TransferReference::TOPIC
+            && event_data::<events::TransferReference>(&event.data)
+                == events::TransferReference {
+                    sender,
+                    spender: Some(spender),
+                    receiver,
+                    value: VALUE,
+                    memo: memo.clone(),
+                }
+    }));
+    assert_eq!(session.account(receiver).balance, 2 * VALUE);
+
+    // the memo is bounded
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "transfer_with_reference",
+        &(
+            receiver,
+            VALUE,
+            vec![0u8; events::TransferReference::MAX_MEMO_SIZE + 1],
+        ),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::MEMO_TOO_LARGE);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+/// Test transfers up to the clearing threshold settle immediately
+/// Test transfers above the clearing threshold are queued
+/// Test approving, rejecting and cancelling pending transfers
+/// Test clearing with an unauthorized account
+#[test]
+fn test_clearing() {
+    const THRESHOLD: u64 = INITIAL_BALANCE / 10;
+    const VALUE: u64 = THRESHOLD * 2;
+
+    let mut session = TestSession::new();
+    let sender = Account::from(*TestSession::PK_1);
+    let agent = Account::from(*TestSession::PK_2);
+    let receiver = Account::from(*TestSession::PK_0);
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_clearing",
+            &(Some(THRESHOLD), Some(agent)),
+        )
+        .expect("Call should pass");
+
+    // transfers up to the threshold settle immediately
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, THRESHOLD),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.account(receiver).balance,
+        INITIAL_OWNERSHIP_BALANCE + THRESHOLD
+    );
+    assert!(session.pending_transfers().is_empty());
+
+    // transfers above the threshold are queued
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, VALUE),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::TransferQueued::TOPIC
+            && event_data::<events::TransferQueued>(&event.data)
+                == events::TransferQueued {
+                    id: 0,
+                    sender,
+                    spender: None,
+                    receiver,
+                    value: VALUE,
+                }
+    }));
+    assert!(!receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::Transfer::TRANSFER_TOPIC));
+    assert_eq!(
+        session.account(sender).balance,
+        INITIAL_BALANCE - THRESHOLD - VALUE,
+        "The queued value should be held in escrow"
+    );
+    assert_eq!(
+        session.account(receiver).balance,
+        INITIAL_OWNERSHIP_BALANCE + THRESHOLD
+    );
+    assert_eq!(
+        session.pending_transfers(),
+        vec![(
+            0,
+            PendingTransfer {
+                sender,
+                spender: None,
+                from_allowance: false,
+                receiver,
+                value: VALUE,
+                memo: None,
+            }
+        )]
+    );
+
+    // unauthorized account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "approve_transfer",
+        &0u64,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the clearing agent approves the transfer
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_2, "approve_transfer", &0u64)
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::TransferCleared::APPROVED_TOPIC));
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::Transfer::TRANSFER_TOPIC));
+    assert_eq!(
+        session.account(receiver).balance,
+        INITIAL_OWNERSHIP_BALANCE + THRESHOLD + VALUE
+    );
+    assert!(session.pending_transfers().is_empty());
+
+    // approving twice fails
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "approve_transfer",
+        &0u64,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::PENDING_TRANSFER_NOT_FOUND);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the clearing agent rejects a transfer
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, VALUE),
+        )
+        .expect("Call should pass");
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "reject_transfer",
+            &(1u64, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::TransferCleared::REJECTED_TOPIC
+            && event_data::<events::TransferCleared>(&event.data).reason
+                == Some(TEST_REASON)
+    }));
+    assert_eq!(
+        session.account(sender).balance,
+        INITIAL_BALANCE - THRESHOLD - VALUE,
+        "The escrowed value should be returned to the sender"
+    );
+
+    // the sender cancels a transfer
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, VALUE),
+        )
+        .expect("Call should pass");
+
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_1, "cancel_transfer", &2u64)
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::TransferCleared::CANCELLED_TOPIC));
+    assert_eq!(
+        session.account(sender).balance,
+        INITIAL_BALANCE - THRESHOLD - VALUE
+    );
+    assert!(session.pending_transfers().is_empty());
+}
+
+/// Test that emptied accounts are pruned
+/// Test that spender lookups don't create account entries
+/// Test compacting legacy empty accounts
+#[test]
+fn test_compact_accounts() {
+    const VALUE: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = TestSession::new();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+    let empty_account = Account::from(ContractId::from_bytes([9; 32]));
+
+    // emptying an account prunes its entry
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(account_2, INITIAL_BALANCE),
+        )
+        .expect("Call should pass");
+
+    // spending an allowance doesn't create an entry for the spender and
+    // prunes the spent allowance
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "approve",
+            &(account_1, VALUE),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer_from",
+            &(account_2, empty_account, 0u64),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer_from",
+            &(account_2, account_2, VALUE),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.allowance(account_2, account_1), 0);
+    assert_eq!(session.account(account_1), AccountInfo::EMPTY);
+    assert_eq!(session.account(account_2).balance, INITIAL_BALANCE);
+
+    // only the ownership can compact the accounts
+    let receipt = session.call_token::<_, (u64, Option<Account>)>(
+        &*TestSession::SK_1,
+        "compact_accounts",
+        &(None::<Account>, 1u32),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // only the entry created by the zero value credit is left to remove,
+    // which is found by compacting the accounts page by page
+    let mut removed = 0;
+    let mut after = None;
+    loop {
+        let (page_removed, last) = session
+            .call_token::<_, (u64, Option<Account>)>(
+                &*TestSession::SK_0,
+                "compact_accounts",
+                &(after, 2u32),
+            )
+            .expect("Call should pass")
+            .data;
+        removed += page_removed;
+
+        if last.is_none() {
+            break;
+        }
+        after = last;
+    }
+    assert_eq!(removed, 1);
+
+    let (removed, _) = session
+        .call_token::<_, (u64, Option<Account>)>(
+            &*TestSession::SK_0,
+            "compact_accounts",
+            &(None::<Account>, u32::MAX),
+        )
+        .expect("Call should pass")
+        .data;
+    assert_eq!(removed, 0);
+
+    // pruned accounts can still be sanctioned
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "block",
+            &(account_1, TEST_REASON),
+        )
+        .expect("Call should pass");
+    assert_eq!(session.account(account_1).status, AccountInfo::BLOCKED);
+}
+
+/// Test creating a currency
+/// Test minting, transferring and pausing a currency independently of the
+/// default currency
+/// Test calling an unknown currency
+#[test]
+fn test_multi_currency() {
+    const CURRENCY: CurrencyId = 1;
+    const VALUE: u64 = 500;
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+    let metadata = CurrencyMetadata {
+        name: String::from("Electronic Money Euro"),
+        symbol: String::from("EURE"),
+        decimals: 2,
+    };
+
+    // only the ownership of the default currency can create currencies
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "create_currency",
+        &(CURRENCY, metadata.clone(), account_1),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "create_currency",
+            &(CURRENCY, metadata.clone(), ownership),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::CurrencyCreated::TOPIC
+            && event_data::<events::CurrencyCreated>(&event.data)
+                == events::CurrencyCreated {
+                    currency: CURRENCY,
+                    metadata: metadata.clone(),
+                    ownership,
+                }
+    }));
+    assert_eq!(session.currencies(), vec![DEFAULT_CURRENCY, CURRENCY]);
+    assert_eq!(
+        session.query_currency::<_, String>(CURRENCY, "currency_name", &()),
+        metadata.name
+    );
+    assert_eq!(
+        session.query_currency::<_, String>(
+            DEFAULT_CURRENCY,
+            "currency_name",
+            &()
+        ),
+        String::from("Electronic Money Token")
+    );
+
+    // currencies can't be created twice
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "create_currency",
+        &(CURRENCY, metadata, ownership),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The currency already exists");
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // mint in the new currency
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "currency_mint",
+            &(CURRENCY, (account_1, VALUE, TEST_REASON)),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::Transfer::MINT_TOPIC
+            && compact::decode_transfer(&event.data)
+                .map(|(_, currency, transfer)| (currency, transfer))
+                .unwrap()
+                == (
+                    CURRENCY,
+                    events::Transfer {
+                        sender: ZERO_ADDRESS,
+                        spender: None,
+                        receiver: account_1,
+                        value: VALUE,
+                        reason: Some(TEST_REASON),
+                    },
+                )
+    }));
+    assert_eq!(
+        session.query_currency::<_, u64>(
+            CURRENCY,
+            "currency_total_supply",
+            &()
+        ),
+        VALUE
+    );
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY);
+
+    // transfer in the new currency
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "currency_transfer",
+            &(CURRENCY, (account_2, VALUE / 2)),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.query_currency::<_, u64>(
+            CURRENCY,
+            "currency_balance_of",
+            &account_2
+        ),
+        VALUE / 2
+    );
+    assert_eq!(session.account(account_1).balance, INITIAL_BALANCE);
+    assert_eq!(session.account(account_2).balance, 0);
+
+    // pausing the new currency doesn't pause the default currency
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "currency_toggle_pause",
+            &(CURRENCY, ()),
+        )
+        .expect("Call should pass");
+
+    assert!(session.query_currency::<_, bool>(
+        CURRENCY,
+        "currency_is_paused",
+        &()
+    ));
+    assert!(!session.is_paused());
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "currency_transfer",
+        &(CURRENCY, (account_2, 1u64)),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::PAUSED_MESSAGE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(account_2, 1u64),
+        )
+        .expect("Call should pass");
+
+    // unknown currency
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "currency_transfer",
+        &(CURRENCY + 1, (account_2, 1u64)),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::CURRENCY_NOT_FOUND);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+/// Test exporting the state in pages
+/// Test importing the state into a new token-contract
+/// Test finalizing the import against the exported supply
+#[test]
+fn test_migration() {
+    const NEW_TOKEN_ID: ContractId = ContractId::from_bytes([3; 32]);
+    const PAGE_SIZE: u32 = 2;
+    const ALLOWANCE: u64 = 100;
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "approve",
+            &(account_2, ALLOWANCE),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "freeze",
+            &(account_1, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    // export the state in pages
+    let mut exported = Vec::new();
+    let mut after = None;
+    loop {
+        let page: Vec<ExportedAccount> = session.query_contract(
+            TOKEN_ID,
+            "export_state",
+            &(after, PAGE_SIZE),
+        );
+        exported.extend(page.iter().cloned());
+
+        if page.len() < PAGE_SIZE as usize {
+            break;
+        }
+        after = page.last().map(|exported| exported.account);
+    }
+
+    assert_eq!(exported.len(), 3);
+    assert_eq!(
+        exported
+            .iter()
+            .map(|exported| exported.balance)
+            .sum::<u64>(),
+        INITIAL_SUPPLY
+    );
+    assert!(exported.contains(&ExportedAccount {
+        account: account_1,
+        balance: INITIAL_BALANCE,
+        status: AccountInfo::FROZEN,
+        sanction_reason: Some(TEST_REASON),
+        allowances: vec![(account_2, ALLOWANCE)],
+        operators: vec![],
+    }));
+
+    // import the state into a new token-contract
+    session.deploy_token(NEW_TOKEN_ID, vec![], ownership);
+
+    let receipt = session.call_contract::<_, ()>(
+        NEW_TOKEN_ID,
+        &*TestSession::SK_0,
+        "import_state",
+        &exported,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(
+            panic_msg,
+            "The state can only be imported while the contract is paused"
+        );
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_contract::<_, ()>(
+            NEW_TOKEN_ID,
+            &*TestSession::SK_0,
+            "toggle_pause",
+            &(),
+        )
+        .expect("Call should pass");
+
+    for page in exported.chunks(PAGE_SIZE as usize) {
+        session
+            .call_contract::<_, ()>(
+                NEW_TOKEN_ID,
+                &*TestSession::SK_0,
+                "import_state",
+                &page.to_vec(),
+            )
+            .expect("Call should pass");
+    }
+
+    // accounts can't be imported twice
+    let receipt = session.call_contract::<_, ()>(
+        NEW_TOKEN_ID,
+        &*TestSession::SK_0,
+        "import_state",
+        &exported[..1].to_vec(),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The account has already been imported");
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // accounts that were pruned after the import can't be imported twice
+    let empty = vec![ExportedAccount {
+        account: Account::from(ContractId::from_bytes([4; 32])),
+        balance: 0,
+        status: AccountInfo::NO_STATUS,
+        sanction_reason: None,
+        allowances: vec![],
+        operators: vec![],
+    }];
+    session
+        .call_contract::<_, ()>(
+            NEW_TOKEN_ID,
+            &*TestSession::SK_0,
+            "import_state",
+            &empty,
+        )
+        .expect("Call should pass");
+
+    let receipt = session.call_contract::<_, ()>(
+        NEW_TOKEN_ID,
+        &*TestSession::SK_0,
+        "import_state",
+        &empty,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The account has already been imported");
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the status must be known and match the sanction reason
+    let invalid = [
+        (AccountInfo::BLOCKED + 1, Some(TEST_REASON)),
+        (AccountInfo::FROZEN, None),
+        (AccountInfo::BLOCKED, None),
+        (AccountInfo::NO_STATUS, Some(TEST_REASON)),
+    ];
+    for (status, sanction_reason) in invalid {
+        let receipt = session.call_contract::<_, ()>(
+            NEW_TOKEN_ID,
+            &*TestSession::SK_0,
+            "import_state",
+            &vec![ExportedAccount {
+                account: Account::from(ContractId::from_bytes([5; 32])),
+                balance: 0,
+                status,
+                sanction_reason,
+                allowances: vec![],
+                operators: vec![],
+            }],
+        );
+
+        if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+            assert_eq!(panic_msg, error::INVALID_IMPORTED_STATUS);
+        } else {
+            panic!("Expected a panic error");
+        }
+    }
+
+    // the import is checked against the exported supply
+    let receipt = session.call_contract::<_, ()>(
+        NEW_TOKEN_ID,
+        &*TestSession::SK_0,
+        "finalize_import",
+        &(INITIAL_SUPPLY + 1),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(
+            panic_msg,
+            "The imported supply doesn't match the exported supply"
+        );
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let exported_supply = session.total_supply();
+    let receipt = session
+        .call_contract::<_, ()>(
+            NEW_TOKEN_ID,
+            &*TestSession::SK_0,
+            "finalize_import",
+            &exported_supply,
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::ImportFinalized::TOPIC
+            && event_data::<events::ImportFinalized>(&event.data)
+                == events::ImportFinalized {
+                    accounts: 4,
+                    supply: INITIAL_SUPPLY,
+                }
+    }));
+
+    // no state can be imported after finalizing
+    let receipt = session.call_contract::<_, ()>(
+        NEW_TOKEN_ID,
+        &*TestSession::SK_0,
+        "import_state",
+        &Vec::<ExportedAccount>::new(),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::IMPORT_FINALIZED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the new token-contract holds the migrated state
+    let new_exported: Vec<ExportedAccount> = session.query_contract(
+        NEW_TOKEN_ID,
+        "export_state",
+        &(None::<Account>, u32::MAX),
+    );
+    assert_eq!(new_exported, exported);
+    assert_eq!(
+        session.query_contract::<_, u64>(NEW_TOKEN_ID, "total_supply", &()),
+        INITIAL_SUPPLY
+    );
+    assert_eq!(
+        session
+            .query_contract::<_, AccountInfo>(
+                NEW_TOKEN_ID,
+                "account",
+                &ownership
+            )
+            .balance,
+        INITIAL_OWNERSHIP_BALANCE
+    );
+
+    // the imported sanctions are accounted for in the supply breakdown
+    let breakdown: SupplyBreakdown =
+        session.query_contract(NEW_TOKEN_ID, "supply_breakdown", &());
+    assert_eq!(breakdown.frozen, INITIAL_BALANCE);
+    assert_eq!(
+        breakdown.circulating,
+        INITIAL_SUPPLY - INITIAL_OWNERSHIP_BALANCE - INITIAL_BALANCE
+    );
+}
+
+/// Test calls from shielded transactions that aren't signed
+/// Test signed calls from shielded transactions
+/// Test replaying signed calls
+/// Test replaying signed calls on another chain
+/// Test replaying signed calls as calls to another function
+/// Test signed calls to unsupported functions
+#[test]
+fn test_signed_call() {
+    const VALUE: u64 = 100;
+    const NOTE_VALUE: u64 = dusk(1.0);
+
+    let mut session = TestSession::new_unverified();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+
+    let mut rng = StdRng::seed_from_u64(0xD0C);
+    let phoenix_sk = PhoenixSecretKey::random(&mut rng);
+    let phoenix_pk = PhoenixPublicKey::from(&phoenix_sk);
+
+    // shielded transactions have no public sender
+    let note = session.fund_phoenix(&phoenix_pk, NOTE_VALUE);
+    let receipt = session.phoenix_call_token::<_, ()>(
+        &phoenix_sk,
+        note,
+        "transfer",
+        &(account_2, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::SHIELDED_NOT_SUPPORTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // calls signed for another chain are rejected
+    assert_eq!(session.signed_call_nonce(*TestSession::PK_1), 0);
+    let other_chain_transfer = SignedCall::new(
+        &TestSession::SK_1,
+        CHAIN_ID + 1,
+        &TOKEN_ID,
+        DEFAULT_CURRENCY,
+        0,
+        "transfer",
+        rkyv::to_bytes::<_, 1024>(&(account_2, VALUE))
+            .unwrap()
+            .to_vec(),
+    );
+
+    let note = session.fund_phoenix(&phoenix_pk, NOTE_VALUE);
+    let receipt = session.phoenix_call_token::<_, ()>(
+        &phoenix_sk,
+        note,
+        "signed_call",
+        &other_chain_transfer,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::INVALID_SIGNATURE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the signer of a call is its sender
+    let signed_transfer = SignedCall::new(
+        &TestSession::SK_1,
+        CHAIN_ID,
+        &TOKEN_ID,
+        DEFAULT_CURRENCY,
+        0,
+        "transfer",
+        rkyv::to_bytes::<_, 1024>(&(account_2, VALUE))
+            .unwrap()
+            .to_vec(),
+    );
+
+    let note = session.fund_phoenix(&phoenix_pk, NOTE_VALUE);
+    let receipt = session
+        .phoenix_call_token::<_, ()>(
+            &phoenix_sk,
+            note,
+            "signed_call",
+            &signed_transfer,
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::Transfer::TRANSFER_TOPIC
+            && compact::decode_transfer(&event.data).unwrap().2
+                == events::Transfer {
+                    sender: account_1,
+                    spender: None,
+                    receiver: account_2,
+                    value: VALUE,
+                    reason: None,
+                }
+    }));
+    assert_eq!(session.balance_of(account_1), INITIAL_BALANCE - VALUE);
+    assert_eq!(session.balance_of(account_2), VALUE);
+    assert_eq!(session.signed_call_nonce(*TestSession::PK_1), 1);
+
+    // a signed call can only be executed once
+    let note = session.fund_phoenix(&phoenix_pk, NOTE_VALUE);
+    let receipt = session.phoenix_call_token::<_, ()>(
+        &phoenix_sk,
+        note,
+        "signed_call",
+        &signed_transfer,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::INVALID_SIGNATURE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // a signed call can't be resubmitted as a call to a function whose name
+    // is a prefix of the signed one, with the arguments shifted accordingly
+    let signed_transfer_from = SignedCall::new(
+        &TestSession::SK_1,
+        CHAIN_ID,
+        &TOKEN_ID,
+        DEFAULT_CURRENCY,
+        1,
+        "transfer_from",
+        rkyv::to_bytes::<_, 1024>(&(account_2, account_1, VALUE))
+            .unwrap()
+            .to_vec(),
+    );
+    let mut shifted_args = b"_from".to_vec();
+    shifted_args.extend(&signed_transfer_from.fn_args);
+    let shifted_transfer = SignedCall {
+        fn_name: String::from("transfer"),
+        fn_args: shifted_args,
+        ..signed_transfer_from
+    };
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "signed_call",
+        &shifted_transfer,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::INVALID_SIGNATURE);
+    } else {
+        panic!("Expected a panic error");
+    }
+    assert_eq!(session.signed_call_nonce(*TestSession::PK_1), 1);
+
+    // signed calls can be relayed by any transaction, but can't be used for
+    // administrative functions
+    let signed_mint = SignedCall::new(
+        &TestSession::SK_0,
+        CHAIN_ID,
+        &TOKEN_ID,
+        DEFAULT_CURRENCY,
+        0,
+        "mint",
+        rkyv::to_bytes::<_, 1024>(&(account_2, VALUE, TEST_REASON))
+            .unwrap()
+            .to_vec(),
+    );
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "signed_call",
+        &signed_mint,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::SIGNED_CALL_NOT_SUPPORTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+/// Test pause
+/// Test transfer from deploy_sk while paused
+/// Test unpause
+/// Test transfer from deploy_sk after unpausing
+/// Test pause with wrong sk
+#[test]
+fn test_pause() {
+    const VALUE: u64 = INITIAL_BALANCE - 1;
+
+    let mut session = TestSession::new();
+    let account_2 = Account::from(*TestSession::PK_2);
+
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "toggle_pause", &())
+        .expect("Call should pass");
+
+    assert_eq!(session.is_paused(), true);
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "transfer",
+        &(account_2, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::PAUSED_MESSAGE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    assert_eq!(
+        session.account(*TestSession::PK_1).balance,
+        INITIAL_BALANCE,
+        "The deployed account should have the initial balance"
+    );
+
+    assert_eq!(
+        session.account(account_2).balance,
+        0,
+        "The account to transfer to should have no balance"
+    );
+
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "toggle_pause", &())
+        .expect("Call should pass");
+
+    assert_eq!(session.is_paused(), false);
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(account_2, VALUE),
+        )
+        .expect("Call should pass");
+
+    // unauthorized account
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_2, "toggle_pause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+/// Test force transfer
+/// Test force transfer with balance too low
+/// Test force transfer with wrong sk
+/// TODO: test force transfer circumventing pause, sanction, etc.
+#[test]
+fn test_force_transfer() {
+    const VALUE: u64 = INITIAL_BALANCE - 1;
+    let mut session = TestSession::new();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+    let ownership_account = Account::from(*TestSession::PK_0);
+
+    // Make a normal transfer from deploy account to the test account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(account_2, VALUE),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.account(account_1).balance,
+        INITIAL_BALANCE - VALUE,
+        "The deployed account should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(account_2).balance,
+        VALUE,
+        "The test account should have the transferred amount"
+    );
+
+    // Force transfer from test account to ownership account
+    let obliged_sender = account_2;
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "force_transfer",
+            &(obliged_sender, ownership_account, VALUE, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.account(account_2).balance,
+        0,
+        "The test account should have the transferred amount subtracted"
+    );
+
+    assert_eq!(
+        session.account(ownership_account).balance,
+        INITIAL_BALANCE + VALUE,
+        "The ownership account should have the transferred amount added"
+    );
+
+    // Force transfer from test account to ownership account again (the
+    // emptied account has been pruned)
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "force_transfer",
+        &(obliged_sender, ownership_account, VALUE, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::ACCOUNT_NOT_FOUND);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Force transfer more than the balance of an account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "force_transfer",
+        &(account_1, ownership_account, VALUE, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::BALANCE_TOO_LOW);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // unauthorized account
+    let obliged_sender = Account::from(ownership_account);
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "force_transfer",
+        &(obliged_sender, account_2, VALUE, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+/// Test block account
+/// Test unfreezing blocked account (fail)
+/// Test transfer to blocked account (fail)
+/// Test transfer from blocked account (fail)
+/// Test freezing blocked account (overriding is allowed)
+/// Test transfer to frozen account (allow)
+/// Test unblocking frozen account (fail)
+/// Test wrong sk for unblock & unfreeze (fail)
+/// Test unfreezing frozen account
+/// Test transfer after unfrozen
+#[test]
+fn test_sanctions() {
+    // TODO: unify transfer logic in the contract so that this implicitly checks
+    // the invariants of transferFrom and any other potential function
+    // leading to a "transfer" that updates the balance
+
+    const VALUE: u64 = INITIAL_BALANCE / 3;
+    let mut session = TestSession::new();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let blocked_account = Account::from(*TestSession::PK_2);
+
+    // Transfer VALUE to test account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(blocked_account, VALUE),
+        )
+        .expect("Call should pass")
+        .data;
+
+    // Block test account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "block",
+            &(blocked_account, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session
+            .call_token::<_, bool>(
+                &*TestSession::SK_2,
+                "blocked",
+                &blocked_account
+            )
+            .expect("Querying the state should succeed")
+            .data,
+        true
+    );
+    assert_eq!(
+        session.sanction_reason(blocked_account),
+        Some(TEST_REASON),
+        "The reason for the sanction should be stored"
+    );
+
+    // Unfreeze blocked test account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "unfreeze",
+        &(blocked_account, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The account is not frozen");
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Transfer VALUE to test account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "transfer",
+        &(blocked_account, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::BLOCKED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Transfer VALUE from test account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "transfer",
+        &(account_1, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::BLOCKED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Freeze test account
+    let frozen_account = blocked_account;
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "freeze",
+            &(frozen_account, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session
+            .call_token::<_, bool>(
+                &*TestSession::SK_2,
+                "frozen",
+                &frozen_account
+            )
+            .expect("Querying the state should succeed")
+            .data,
+        true
+    );
+
+    // Transfer VALUE to test account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(frozen_account, VALUE),
+        )
+        .expect("Transfer to frozen account should succeed");
+
+    // Transfer VALUE from test account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "transfer",
+        &(account_1, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::FROZEN);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Unblock frozen test account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "unblock",
+        &(frozen_account, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The account is not blocked");
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Unauthorized account
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "unblock",
+        &(frozen_account, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "unfreeze",
+        &(frozen_account, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // Unfreeze test account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "unfreeze",
+            &(frozen_account, TEST_REASON),
+        )
+        .expect("Unfreezing should succeed");
+
+    assert_eq!(
+        session.sanction_reason(frozen_account),
+        None,
+        "The reason should be removed with the sanction"
+    );
+
+    // Transfer VALUE from test account
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "transfer",
+            &(account_1, VALUE),
+        )
+        .expect("Transfer should succeed again");
+}
+
+#[test]
+fn test_can_transfer() {
+    const ALLOWANCE: u64 = INITIAL_BALANCE / 10;
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+
+    assert_eq!(
+        session.can_transfer(account_1, account_2, INITIAL_BALANCE),
+        TransferStatus::Allowed
+    );
+    assert_eq!(
+        session.can_transfer(account_1, account_2, INITIAL_BALANCE + 1),
+        TransferStatus::BalanceTooLow
+    );
+    assert_eq!(
+        session.can_transfer(account_2, account_1, 1),
+        TransferStatus::AccountNotFound
+    );
+    assert_eq!(
+        session.can_transfer(account_1, ZERO_ADDRESS, 1),
+        TransferStatus::ReservedAddress
+    );
+
+    // allowances
+    assert_eq!(
+        session.can_transfer_from(account_2, account_1, ownership, 1),
+        TransferStatus::NoAllowances
+    );
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "approve",
+            &(account_2, ALLOWANCE),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_transfer_from(ownership, account_1, account_2, 1),
+        TransferStatus::SpenderNotAllowed
+    );
+    assert_eq!(
+        session.can_transfer_from(account_2, account_1, ownership, ALLOWANCE),
+        TransferStatus::Allowed
+    );
+    assert_eq!(
+        session.can_transfer_from(
+            account_2,
+            account_1,
+            ownership,
+            ALLOWANCE + 1
+        ),
+        TransferStatus::AllowanceTooLow
+    );
+
+    // sanctions
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "block",
+            &(account_2, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_transfer(account_1, account_2, 1),
+        TransferStatus::ReceiverBlocked
+    );
+
+    let status = session.can_transfer_from(account_2, account_1, ownership, 1);
+    assert_eq!(status, TransferStatus::SpenderBlocked);
+
+    // the preflight status matches the outcome of the transfer
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "transfer_from",
+        &(account_1, ownership, 1u64),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(Some(panic_msg.as_str()), status.error());
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "unblock",
+            &(account_2, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "freeze",
+            &(account_1, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_transfer(account_1, account_2, 1),
+        TransferStatus::SenderFrozen
+    );
+    assert_eq!(
+        session.can_transfer_from(account_2, account_1, ownership, 1),
+        TransferStatus::SenderFrozen
+    );
+    assert_eq!(
+        session.can_transfer(ownership, account_1, 1),
+        TransferStatus::Allowed,
+        "Frozen accounts can still receive"
+    );
+
+    // clearing
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_clearing",
+            &(Some(ALLOWANCE), Some(ownership)),
+        )
+        .expect("Call should pass");
+
+    let status = session.can_transfer(ownership, account_2, ALLOWANCE + 1);
+    assert_eq!(status, TransferStatus::RequiresClearing);
+    assert!(status.is_allowed());
+
+    // transfers that would be vetoed are not reported as requiring clearing
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "block",
+            &(account_2, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    let status = session.can_transfer(ownership, account_2, ALLOWANCE + 1);
+    assert_eq!(status, TransferStatus::ReceiverBlocked);
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "transfer",
+        &(account_2, ALLOWANCE + 1),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(Some(panic_msg.as_str()), status.error());
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "unblock",
+            &(account_2, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    // pause
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "toggle_pause", &())
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_transfer(ownership, account_2, 1),
+        TransferStatus::Paused
+    );
+    assert_eq!(
+        session.can_transfer_from(account_2, account_1, ownership, 1),
+        TransferStatus::Paused
+    );
+}
+
+#[test]
+fn test_compliance() {
+    const VALUE: u64 = INITIAL_BALANCE / 10;
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let account_2 = Account::from(*TestSession::PK_2);
+
+    // only the ownership can register a compliance contract
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "set_compliance",
+        &Some(COMPLIANCE_ID),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_compliance",
+            &Some(COMPLIANCE_ID),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::ComplianceUpdated::TOPIC
+            && event_data::<events::ComplianceUpdated>(&event.data)
+                == events::ComplianceUpdated {
+                    compliance: Some(COMPLIANCE_ID),
+                }
+    }));
+    assert_eq!(
+        session.query_contract::<_, Option<ContractId>>(
+            TOKEN_ID,
+            "compliance",
+            &()
+        ),
+        Some(COMPLIANCE_ID)
+    );
+
+    // executed operations are notified to the compliance contract
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(account_2, VALUE),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "mint",
+            &(account_1, VALUE, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "burn", &(VALUE, TEST_REASON))
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.query_contract::<_, Vec<(Account, Account, u64)>>(
+            COMPLIANCE_ID,
+            "transfers",
+            &()
+        ),
+        vec![
+            (account_1, account_2, VALUE),
+            (ZERO_ADDRESS, account_1, VALUE),
+            (ownership, ZERO_ADDRESS, VALUE),
+        ]
+    );
+
+    // operations involving a restricted account are vetoed
+    session
+        .call_contract::<_, ()>(
+            COMPLIANCE_ID,
+            &*TestSession::SK_0,
+            "restrict",
+            &account_2,
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_transfer(account_1, account_2, VALUE),
+        TransferStatus::ComplianceRejected
+    );
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "transfer",
+        &(account_2, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::COMPLIANCE_REJECTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "transfer",
+        &(account_1, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::COMPLIANCE_REJECTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "mint",
+        &(account_2, VALUE, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::COMPLIANCE_REJECTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    assert_eq!(session.account(account_2).balance, VALUE);
+
+    // executing a hold and requesting a redemption are vetoed as well
+    let hold_id = session
+        .call_token::<_, u64>(
+            &*TestSession::SK_1,
+            "hold",
+            &(account_2, ownership, VALUE, 0u64),
+        )
+        .expect("Call should pass")
+        .data;
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "execute_hold",
+        &(hold_id, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::COMPLIANCE_REJECTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "release_hold", &hold_id)
+        .expect("Call should pass");
+
+    let receipt = session.call_token::<_, u64>(
+        &*TestSession::SK_2,
+        "request_redemption",
+        &(VALUE, [0u8; 32]),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::COMPLIANCE_REJECTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // administrative actions are not vetoed, but notified
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "force_transfer",
+            &(account_2, account_1, VALUE / 2, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "controller_redeem",
+            &(account_2, VALUE / 2, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.account(account_2).balance, 0);
+    assert!(session
+        .query_contract::<_, Vec<(Account, Account, u64)>>(
+            COMPLIANCE_ID,
+            "transfers",
+            &()
+        )
+        .ends_with(&[
+            (account_2, account_1, VALUE / 2),
+            (account_2, ZERO_ADDRESS, VALUE / 2),
+        ]));
+
+    // removing the compliance contract lifts its restrictions
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_compliance",
+            &None::<ContractId>,
+        )
+        .expect("Call should pass");
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(account_2, VALUE),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.account(account_2).balance, VALUE);
+}
+
+#[test]
+fn test_attributes() {
+    const VALUE: u64 = INITIAL_BALANCE / 10;
+    const DE: Jurisdiction = *b"DE";
+    const US: Jurisdiction = *b"US";
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let manager = Account::from(*TestSession::PK_2);
+
+    let attributes = AccountAttributes {
+        jurisdiction: Some(DE),
+        investor_tier: Some(1),
+        risk_score: Some(3),
+    };
+
+    // only the ownership and the attribute manager can set attributes
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "set_attributes",
+        &(account_1, attributes),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_attribute_manager",
+            &Some(manager),
+        )
+        .expect("Call should pass");
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "set_attributes",
+            &(account_1, attributes),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::AttributesUpdated::TOPIC
+            && event_data::<events::AttributesUpdated>(&event.data)
+                == events::AttributesUpdated {
+                    account: account_1,
+                    attributes,
+                }
+    }));
+    assert_eq!(
+        session.query_contract::<_, AccountAttributes>(
+            TOKEN_ID,
+            "attributes",
+            &account_1
+        ),
+        attributes
+    );
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "set_attributes",
+            &(
+                ownership,
+                AccountAttributes {
+                    jurisdiction: Some(US),
+                    ..AccountAttributes::EMPTY
+                },
+            ),
+        )
+        .expect("Call should pass");
+
+    // a hold put before the restriction
+    let hold_id = session
+        .call_token::<_, u64>(
+            &*TestSession::SK_1,
+            "hold",
+            &(ownership, manager, VALUE, 0u64),
+        )
+        .expect("Call should pass")
+        .data;
+
+    // only the ownership can restrict jurisdictions
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "restrict_jurisdictions",
+        &(DE, US),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "restrict_jurisdictions",
+            &(DE, US),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_transfer(account_1, ownership, VALUE),
+        TransferStatus::JurisdictionRestricted
+    );
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "transfer",
+        &(ownership, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::JURISDICTION_RESTRICTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // holds can't be executed across restricted jurisdictions either
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "execute_hold",
+        &(hold_id, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::JURISDICTION_RESTRICTED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(&*TestSession::SK_2, "release_hold", &hold_id)
+        .expect("Call should pass");
+
+    // the restriction only applies in one direction
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "transfer",
+            &(account_1, VALUE),
+        )
+        .expect("Call should pass");
+
+    // accounts without a jurisdiction are not restricted
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "set_attributes",
+            &(account_1, AccountAttributes::EMPTY),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.query_contract::<_, AccountAttributes>(
+            TOKEN_ID,
+            "attributes",
+            &account_1
+        ),
+        AccountAttributes::EMPTY
+    );
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(ownership, VALUE),
+        )
+        .expect("Call should pass");
+
+    // lifting the restriction
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "unrestrict_jurisdictions",
+            &(DE, US),
+        )
+        .expect("Call should pass");
+
+    assert!(!session.query_contract::<_, bool>(
+        TOKEN_ID,
+        "jurisdictions_restricted",
+        &(DE, US)
+    ));
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "unrestrict_jurisdictions",
+        &(DE, US),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The jurisdictions are not restricted");
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+#[test]
+fn test_documents() {
+    const WHITE_PAPER: &str = "white_paper";
+    const TERMS: &str = "terms";
+    const WHITE_PAPER_URI: &str = "https://example.com/white-paper.pdf";
+    const TERMS_URI: &str = "https://example.com/terms.pdf";
+
+    let mut session = TestSession::new();
+
+    // only the ownership can set documents
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "set_document",
+        &(
+            String::from(WHITE_PAPER),
+            String::from(WHITE_PAPER_URI),
+            [1u8; 32],
+        ),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the name and the URI are bounded
+    for (name, uri) in [
+        (
+            "n".repeat(Document::MAX_NAME_SIZE + 1),
+            String::from(WHITE_PAPER_URI),
+        ),
+        (
+            String::from(WHITE_PAPER),
+            "u".repeat(Document::MAX_URI_SIZE + 1),
+        ),
+    ] {
+        let receipt = session.call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_document",
+            &(name, uri, [1u8; 32]),
+        );
+
+        if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+            assert_eq!(panic_msg, error::DOCUMENT_TOO_LARGE);
+        } else {
+            panic!("Expected a panic error");
+        }
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_document",
+            &(
+                String::from(WHITE_PAPER),
+                String::from(WHITE_PAPER_URI),
+                [1u8; 32],
+            ),
+        )
+        .expect("Call should pass");
+
+    let event = receipt
+        .events
+        .iter()
+        .find(|event| event.topic == events::DocumentUpdated::TOPIC)
+        .expect("The document event should be emitted");
+    let event = event_data::<events::DocumentUpdated>(&event.data);
+    assert_eq!(event.name, WHITE_PAPER);
+    assert_eq!(event.document.uri, WHITE_PAPER_URI);
+    assert_eq!(event.document.hash, [1u8; 32]);
+
+    let white_paper = session
+        .query_contract::<_, Option<Document>>(
+            TOKEN_ID,
+            "document",
+            &String::from(WHITE_PAPER),
+        )
+        .expect("The document should be registered");
+    assert_eq!(white_paper, event.document);
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_document",
+            &(String::from(TERMS), String::from(TERMS_URI), [2u8; 32]),
+        )
+        .expect("Call should pass");
+
+    let documents = session.query_contract::<_, Vec<(String, Document)>>(
+        TOKEN_ID,
+        "documents",
+        &(),
+    );
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0].0, TERMS);
+    assert_eq!(documents[0].1.uri, TERMS_URI);
+    assert_eq!(documents[1], (String::from(WHITE_PAPER), white_paper));
+
+    // removing a document
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "remove_document",
+            &String::from(TERMS),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::DocumentUpdated::REMOVED_TOPIC));
+    assert_eq!(
+        session.query_contract::<_, Option<Document>>(
+            TOKEN_ID,
+            "document",
+            &String::from(TERMS),
+        ),
+        None
+    );
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "remove_document",
+        &String::from(TERMS),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::DOCUMENT_NOT_FOUND);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+#[test]
+fn test_reserve_attestations() {
+    const VALIDITY: u64 = 10;
+    const VALUE: u64 = INITIAL_BALANCE / 10;
+    const TIMESTAMP: u64 = 1_700_000_000;
+    const REPORT_HASH: [u8; 32] = [7; 32];
+
+    let mut session = TestSession::new();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let attestor = Account::from(*TestSession::PK_2);
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_attestor",
+            &(Some(attestor), Some(VALIDITY)),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::AttestorUpdated::TOPIC
+            && event_data::<events::AttestorUpdated>(&event.data)
+                == events::AttestorUpdated {
+                    attestor: Some(attestor),
+                    validity: Some(VALIDITY),
+                }
+    }));
+
+    // minting requires an attestation
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "mint",
+        &(account_1, VALUE, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::ATTESTATION_NOT_FOUND);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // only the attestor can publish attestations
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "attest_reserves",
+        &(INITIAL_SUPPLY + VALUE, TIMESTAMP, REPORT_HASH),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "attest_reserves",
+            &(INITIAL_SUPPLY + VALUE, TIMESTAMP, REPORT_HASH),
+        )
+        .expect("Call should pass");
+
+    let attestation = session
+        .query_contract::<_, Option<ReserveAttestation>>(
+            TOKEN_ID,
+            "reserve_attestation",
+            &(),
+        )
+        .expect("The attestation should be published");
+    assert_eq!(attestation.reserves, INITIAL_SUPPLY + VALUE);
+    assert_eq!(attestation.timestamp, TIMESTAMP);
+    assert_eq!(attestation.report_hash, REPORT_HASH);
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::ReservesAttested::TOPIC
+            && event_data::<events::ReservesAttested>(&event.data)
+                == events::ReservesAttested {
+                    attestor,
+                    attestation,
+                }
+    }));
+
+    // the supply can be minted up to the reserves
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "mint",
+            &(account_1, VALUE, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "mint",
+        &(account_1, 1u64, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::RESERVES_EXCEEDED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // attestations can't go back in time
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "attest_reserves",
+        &(INITIAL_SUPPLY + 2 * VALUE, TIMESTAMP, REPORT_HASH),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::ATTESTATION_OUTDATED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "attest_reserves",
+            &(INITIAL_SUPPLY + 3 * VALUE, TIMESTAMP + 1, REPORT_HASH),
+        )
+        .expect("Call should pass");
+
+    // the attestation is valid for the configured number of blocks
+    session.advance_block_height(VALIDITY);
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "mint",
+            &(account_1, VALUE, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    session.advance_block_height(1);
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_0,
+        "mint",
+        &(account_1, VALUE, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::ATTESTATION_STALE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // removing the attestor lifts the gate
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_attestor",
+            &(None::<Account>, None::<u64>),
+        )
+        .expect("Call should pass");
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "mint",
+            &(account_1, VALUE, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(session.total_supply(), INITIAL_SUPPLY + 3 * VALUE);
+}
+
+#[test]
+fn test_guardian() {
+    let mut session = TestSession::new();
+    let account_1 = Account::from(*TestSession::PK_1);
+    let guardian = Account::from(*TestSession::PK_2);
+
+    // only the ownership can add guardians
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "add_guardian",
+        &guardian,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_0, "add_guardian", &guardian)
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::GuardianUpdated::ADDED_TOPIC
+            && event_data::<events::GuardianUpdated>(&event.data)
+                == events::GuardianUpdated { guardian }
+    }));
+    assert!(session.query_contract::<_, bool>(
+        TOKEN_ID,
+        "is_guardian",
+        &guardian
+    ));
+
+    // other accounts can't pause
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_1, "pause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // the guardian can pause
+    session
+        .call_token::<_, ()>(&*TestSession::SK_2, "pause", &())
+        .expect("Call should pass");
+
+    assert_eq!(session.is_paused(), true);
+
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_2, "pause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::PAUSED_MESSAGE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // but the guardian can't unpause, mint or change sanctions
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_2, "unpause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_2, "toggle_pause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "mint",
+        &(guardian, 1u64, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "block",
+        &(account_1, TEST_REASON),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    assert_eq!(session.is_paused(), true);
+
+    // only the ownership can unpause
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "unpause", &())
+        .expect("Call should pass");
+
+    assert_eq!(session.is_paused(), false);
+
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_0, "unpause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::NOT_PAUSED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // removed guardians can no longer pause
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_0, "remove_guardian", &guardian)
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::GuardianUpdated::REMOVED_TOPIC));
+    assert!(session
+        .query_contract::<_, Vec<Account>>(TOKEN_ID, "guardians", &())
+        .is_empty());
+
+    let receipt =
+        session.call_token::<_, ()>(&*TestSession::SK_2, "pause", &());
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::UNAUTHORIZED_ACCOUNT);
+    } else {
+        panic!("Expected a panic error");
+    }
+}
+
+#[test]
+fn test_operators() {
+    const VALUE: u64 = INITIAL_BALANCE / 10;
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+    let operator = Account::from(*TestSession::PK_2);
+
+    // accounts without an operator can't be sent from
+    assert!(!session.is_operator_for(operator, account_1));
+    assert_eq!(
+        session.can_operator_send(operator, account_1, ownership, VALUE),
+        TransferStatus::NotOperator
+    );
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "operator_send",
+        &(account_1, ownership, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::NOT_OPERATOR);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // holders can't be their own operator
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "authorize_operator",
+        &account_1,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, "The holder can't be its own operator");
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "authorize_operator",
+            &operator,
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::OperatorUpdated::AUTHORIZED_TOPIC
+            && event_data::<events::OperatorUpdated>(&event.data)
+                == events::OperatorUpdated {
+                    holder: account_1,
+                    operator,
+                }
+    }));
+    assert!(session.is_operator_for(operator, account_1));
+    assert!(!session.is_operator_for(account_1, operator));
+
+    // the operator can send the full balance of the holder
+    assert_eq!(
+        session.can_operator_send(
+            operator,
+            account_1,
+            ownership,
+            INITIAL_BALANCE + 1
+        ),
+        TransferStatus::BalanceTooLow
+    );
+
+    let receipt = session
+        .call_token::<_, ()>(
+            &*TestSession::SK_2,
+            "operator_send",
+            &(account_1, ownership, VALUE),
+        )
+        .expect("Call should pass");
+
+    assert!(receipt.events.iter().any(|event| {
+        event.topic == events::Transfer::OPERATOR_SEND_TOPIC
+            && compact::decode_transfer(&event.data).unwrap().2
+                == events::Transfer {
+                    sender: account_1,
+                    spender: Some(operator),
+                    receiver: ownership,
+                    value: VALUE,
+                    reason: None,
+                }
+    }));
+    assert!(!receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::Transfer::TRANSFER_TOPIC));
+    assert_eq!(session.balance_of(account_1), INITIAL_BALANCE - VALUE);
+    assert_eq!(
+        session.balance_of(ownership),
+        INITIAL_OWNERSHIP_BALANCE + VALUE
+    );
+
+    // operators follow the same sanction rules as transfers
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "freeze",
+            &(account_1, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    assert_eq!(
+        session.can_operator_send(operator, account_1, ownership, VALUE),
+        TransferStatus::SenderFrozen
+    );
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "unfreeze",
+            &(account_1, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "block",
+            &(operator, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "operator_send",
+        &(account_1, ownership, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::BLOCKED);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "unblock",
+            &(operator, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "pause", &())
+        .expect("Call should pass");
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "operator_send",
+        &(account_1, ownership, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::PAUSED_MESSAGE);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    session
+        .call_token::<_, ()>(&*TestSession::SK_0, "unpause", &())
+        .expect("Call should pass");
+
+    // revoked operators can no longer send
+    let receipt = session
+        .call_token::<_, ()>(&*TestSession::SK_1, "revoke_operator", &operator)
+        .expect("Call should pass");
+
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == events::OperatorUpdated::REVOKED_TOPIC));
+    assert!(!session.is_operator_for(operator, account_1));
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_2,
+        "operator_send",
+        &(account_1, ownership, VALUE),
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::NOT_OPERATOR);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    let receipt = session.call_token::<_, ()>(
+        &*TestSession::SK_1,
+        "revoke_operator",
+        &operator,
+    );
+
+    if let ContractError::Panic(panic_msg) = receipt.unwrap_err() {
+        assert_eq!(panic_msg, error::NOT_OPERATOR);
+    } else {
+        panic!("Expected a panic error");
+    }
+
+    // rejected and cancelled operator sends don't grant an allowance
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "authorize_operator",
+            &operator,
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "set_clearing",
+            &(Some(VALUE / 2), None::<Account>),
+        )
+        .expect("Call should pass");
+
+    for id in 0..2u64 {
+        session
+            .call_token::<_, ()>(
+                &*TestSession::SK_2,
+                "operator_send",
+                &(account_1, ownership, VALUE),
+            )
+            .expect("Call should pass");
+
+        assert_eq!(
+            session.pending_transfers(),
+            vec![(
+                id,
+                PendingTransfer {
+                    sender: account_1,
+                    spender: Some(operator),
+                    from_allowance: false,
+                    receiver: ownership,
+                    value: VALUE,
+                    memo: None,
+                }
+            )]
+        );
+
+        if id == 0 {
+            session
+                .call_token::<_, ()>(
+                    &*TestSession::SK_0,
+                    "reject_transfer",
+                    &(id, TEST_REASON),
+                )
+                .expect("Call should pass");
+        } else {
+            session
+                .call_token::<_, ()>(
+                    &*TestSession::SK_2,
+                    "cancel_transfer",
+                    &id,
+                )
+                .expect("Call should pass");
+        }
+
+        assert!(session.pending_transfers().is_empty());
+        assert_eq!(session.balance_of(account_1), INITIAL_BALANCE - VALUE);
+        assert_eq!(session.allowance(account_1, operator), 0);
+    }
+}
+
+/// Test the breakdown of the supply and the running mint and burn counters.
+#[test]
+fn test_supply_breakdown() {
+    const REDEEM_AMOUNT: u64 = INITIAL_BALANCE / 10;
+    const MINT_AMOUNT: u64 = 500;
+    const BURN_AMOUNT: u64 = 200;
+
+    let mut session = TestSession::new();
+    let blocked = Account::from(*TestSession::PK_2);
+
+    assert_eq!(
+        session.supply_breakdown(),
+        SupplyBreakdown {
+            supply: INITIAL_SUPPLY,
+            minted: u128::from(INITIAL_SUPPLY),
+            burned: 0,
+            ownership: INITIAL_OWNERSHIP_BALANCE,
+            frozen: 0,
+            blocked: 0,
+            escrowed: 0,
+            circulating: INITIAL_BALANCE + INITIAL_HOLDER_BALANCE,
+        }
+    );
+
+    let redemption_id = session
+        .call_token::<_, u64>(
+            &*TestSession::SK_1,
+            "request_redemption",
+            &(REDEEM_AMOUNT, [1u8; 32]),
+        )
+        .expect("Call should pass")
+        .data;
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "freeze",
+            &(Account::from(HOLDER_ID), TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "mint",
+            &(blocked, MINT_AMOUNT, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "block",
+            &(blocked, TEST_REASON),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "burn",
+            &(BURN_AMOUNT, TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    let supply = INITIAL_SUPPLY + MINT_AMOUNT - BURN_AMOUNT;
+    assert_eq!(
+        session.supply_breakdown(),
+        SupplyBreakdown {
+            supply,
+            minted: u128::from(INITIAL_SUPPLY + MINT_AMOUNT),
+            burned: u128::from(BURN_AMOUNT),
+            ownership: INITIAL_OWNERSHIP_BALANCE - BURN_AMOUNT,
+            frozen: INITIAL_HOLDER_BALANCE,
+            blocked: MINT_AMOUNT,
+            escrowed: REDEEM_AMOUNT,
+            circulating: INITIAL_BALANCE - REDEEM_AMOUNT,
+        }
+    );
+
+    // settling the redemption burns the escrowed tokens
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "settle_redemption",
+            &(redemption_id, [2u8; 32]),
+        )
+        .expect("Call should pass");
+
+    let breakdown = session.supply_breakdown();
+    assert_eq!(breakdown.supply, supply - REDEEM_AMOUNT);
+    assert_eq!(breakdown.burned, u128::from(BURN_AMOUNT + REDEEM_AMOUNT));
+    assert_eq!(breakdown.escrowed, 0);
+    assert_eq!(breakdown.circulating, INITIAL_BALANCE - REDEEM_AMOUNT);
+}
+
+/// Test checking the invariants of the state in pages.
+#[test]
+fn test_verify_invariants() {
+    const REDEEM_AMOUNT: u64 = INITIAL_BALANCE / 10;
+
+    let mut session = TestSession::new();
+
+    session
+        .call_token::<_, u64>(
+            &*TestSession::SK_1,
+            "request_redemption",
+            &(REDEEM_AMOUNT, [1u8; 32]),
+        )
+        .expect("Call should pass");
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "freeze",
+            &(Account::from(HOLDER_ID), TEST_REASON),
+        )
+        .expect("Call should pass");
+
+    // all accounts at once
+    let report = session.verify_invariants(None, 0, u32::MAX);
+    assert!(report.complete);
+    assert_eq!(report.violations, vec![]);
+    assert_eq!(report.balances, INITIAL_SUPPLY - REDEEM_AMOUNT);
+
+    // one account per page
+    let mut page = session.verify_invariants(None, 0, 1);
+    let mut pages = 1;
+    while !page.complete {
+        assert_eq!(page.violations, vec![]);
+        page = session.verify_invariants(page.last, page.balances, 1);
+        pages += 1;
+    }
+    assert_eq!(page, report);
+    assert_eq!(
+        pages, 3,
+        "The ownership, holder and sender should be checked"
+    );
+
+    // a page of no accounts makes no progress
+    let report = session.verify_invariants(None, 0, 0);
+    assert!(!report.complete);
+    assert_eq!(report.balances, 0);
+}
+
+/// Test the proofs of balances against the root of the Merkle tree over the
+/// balances.
+#[test]
+fn test_balance_proofs() {
+    const VALUE: u64 = 100;
+
+    let mut session = TestSession::new();
+    let sender = Account::from(*TestSession::PK_1);
+    let receiver = Account::from(*TestSession::PK_2);
+
+    let root = session.balances_root();
+    for account in [sender, HOLDER_ID.into(), session.ownership()] {
+        let proof = session
+            .balance_proof(account)
+            .expect("The account should have a proof");
+        assert_eq!(proof.account_info, session.account(account));
+        assert!(proof.verify(&root, account));
+    }
+    assert_eq!(session.balance_proof(receiver), None);
+
+    let old_proof = session.balance_proof(sender).unwrap();
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, VALUE),
+        )
+        .expect("Call should pass");
+
+    let root = session.balances_root();
+    assert!(
+        !old_proof.verify(&root, sender),
+        "The old proof should fail"
+    );
+
+    let proof = session.balance_proof(sender).unwrap();
+    assert_eq!(proof.account_info.balance, INITIAL_BALANCE - VALUE);
+    assert!(proof.verify(&root, sender));
+    assert!(!proof.verify(&root, receiver), "The proof is of the sender");
+
+    let mut forged = proof.clone();
+    forged.account_info.balance += VALUE;
+    assert!(!forged.verify(&root, sender), "A forged proof should fail");
+
+    let proof = session.balance_proof(receiver).unwrap();
+    assert_eq!(proof.account_info.balance, VALUE);
+    assert!(proof.verify(&root, receiver));
+    let receiver_position = proof.position;
+
+    // the leaf of a pruned account is removed and its position reused
+    session
+        .call_token::<_, ()>(&*TestSession::SK_2, "transfer", &(sender, VALUE))
+        .expect("Call should pass");
+    assert_eq!(session.balance_proof(receiver), None);
+    let root = session.balances_root();
+    assert!(session.balance_proof(sender).unwrap().verify(&root, sender));
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_1,
+            "transfer",
+            &(receiver, VALUE),
+        )
+        .expect("Call should pass");
+    let root = session.balances_root();
+    let proof = session.balance_proof(receiver).unwrap();
+    assert_eq!(proof.position, receiver_position);
+    assert!(proof.verify(&root, receiver));
+}
+
+/// Measure the gas spent by `transfer` and `transfer_from` as the number of
+/// accounts in the state grows.
+///
+/// The state is filled by importing accounts with distinct, unchecked keys,
+/// since deriving that many keys from secret keys would take too long. The
+/// accounts are imported in batches as large as the buffer the test session
+/// serializes the arguments of a transaction into allows, with a gas limit
+/// high enough for a whole batch.
+///
+/// Run with `cargo test --release -- --ignored --nocapture gas_at_scale`.
+#[test]
+#[ignore = "benchmark filling the state with a million accounts"]
+fn gas_at_scale() {
+    const BATCH: u64 = 12;
+    const IMPORT_GAS_LIMIT: u64 = 1 << 34;
+    const SIZES: [u64; 3] = [10_000, 100_000, 1_000_000];
+
+    let mut session = TestSession::new();
+    let ownership = Account::from(*TestSession::PK_0);
+    let account_1 = Account::from(*TestSession::PK_1);
+
+    session
+        .call_token::<_, ()>(
+            &*TestSession::SK_0,
+            "approve",
+            &(account_1, INITIAL_OWNERSHIP_BALANCE),
+        )
+        .expect("Call should pass");
+
+    let raw = TestSession::PK_2.to_raw_bytes();
+    let mut imported = 0;
+
+    for size in SIZES {
+        session
+            .call_token::<_, ()>(&*TestSession::SK_0, "pause", &())
+            .expect("Call should pass");
+
+        while imported < size {
+            let batch = (imported + BATCH).min(size);
+            let accounts: Vec<ExportedAccount> = (imported..batch)
+                .map(|i| {
+                    let mut raw = raw;
+                    raw[..8].copy_from_slice(&i.to_le_bytes());
+                    // SAFETY: the keys are only used as labels and are never
+                    // used to verify signatures
+                    let pk =
+                        unsafe { AccountPublicKey::from_slice_unchecked(&raw) };
+
+                    ExportedAccount {
+                        account: Account::from(pk),
+                        balance: 1,
+                        status: AccountInfo::NO_STATUS,
+                        sanction_reason: None,
+                        allowances: vec![],
+                        operators: vec![],
+                    }
+                })
+                .collect();
+
+            session
+                .call_token_with_gas_limit::<_, ()>(
+                    &*TestSession::SK_0,
+                    "import_state",
+                    &accounts,
+                    IMPORT_GAS_LIMIT,
+                )
+                .expect("Call should pass");
+            imported = batch;
+        }
+
+        session
+            .call_token::<_, ()>(&*TestSession::SK_0, "unpause", &())
+            .expect("Call should pass");
+
+        let transfer = session
+            .call_token::<_, ()>(
+                &*TestSession::SK_1,
+                "transfer",
+                &(ownership, 1u64),
+            )
+            .expect("Call should pass");
+        let transfer_from = session
+            .call_token::<_, ()>(
+                &*TestSession::SK_1,
+                "transfer_from",
+                &(ownership, account_1, 1u64),
+            )
+            .expect("Call should pass");
+
+        // the gas spent by a transaction calling the token for nothing, to
+        // tell the gas spent by the token apart from the transaction overhead
+        let overhead = session
+            .call_token::<_, u64>(&*TestSession::SK_1, "total_supply", &())
+            .expect("Call should pass");
+
+        println!(
+            "{size} accounts: transfer {} gas, transfer_from {} gas, \
+             overhead {} gas",
+            transfer.gas_spent, transfer_from.gas_spent, overhead.gas_spent
+        );
+    }
 }